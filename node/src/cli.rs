@@ -35,6 +35,15 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Convert between Open Badges 3.0 credentials and certificates.
+	OpenBadge(crate::open_badge::OpenBadgeCmd),
+
+	/// Check a document against the certificates anchored on chain.
+	VerifyDocument(crate::verify_document::VerifyDocumentCmd),
+
+	/// Encrypt and decrypt CV items with keys of the local keystore.
+	CvCrypto(crate::cv_crypto::CvCryptoCmd),
+
 	/// The custom benchmark subcommand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
@@ -1,6 +1,7 @@
 use crate::{
 	chain_spec,
 	cli::{Cli, Subcommand},
+	open_badge::OpenBadgeCmd,
 	service,
 };
 use scv_node::Block;
@@ -98,6 +99,16 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend), task_manager))
 			})
 		},
+		Some(Subcommand::OpenBadge(OpenBadgeCmd::Import(cmd))) => cmd.run(),
+		Some(Subcommand::OpenBadge(OpenBadgeCmd::Export(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = service::new_partial(&config)?;
+				cmd.run(client)
+			})
+		},
+		Some(Subcommand::VerifyDocument(cmd)) => cmd.run(),
+		Some(Subcommand::CvCrypto(cmd)) => cmd.run(),
 		Some(Subcommand::Benchmark(cmd)) =>
 			if cfg!(feature = "runtime-benchmarks") {
 				let runner = cli.create_runner(cmd)?;
@@ -0,0 +1,366 @@
+//! The `cv-crypto` subcommand, encrypting CV items with keys of the local keystore.
+//!
+//! Item metadata is encrypted with ChaCha20-Poly1305 under a random content key and stored as
+//! the 12-byte nonce followed by the ciphertext. The content key is sealed to each reader: an
+//! ephemeral x25519 key agrees a key with the reader's registered x25519 key, which encrypts the
+//! content key. Envelopes are the ephemeral public key followed by the encrypted content key.
+
+use std::{io::Write, path::PathBuf};
+
+use chacha20poly1305::{
+	aead::{Aead, NewAead},
+	ChaCha20Poly1305, Key, Nonce,
+};
+use pallet_cv::ENVELOPE_LENGTH;
+use serde_json::json;
+use sp_core::{
+	crypto::{KeyTypeId, Pair as _, Ss58Codec},
+	hashing::blake2_256,
+	sr25519,
+};
+use structopt::StructOpt;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+const NONCE_LENGTH: usize = 12;
+
+/// Encrypt and decrypt CV items with keys of the local keystore.
+#[derive(Debug, StructOpt)]
+pub enum CvCryptoCmd {
+	/// Print the x25519 key to register with `set_encryption_key`.
+	PublicKey(PublicKeyCmd),
+	/// Encrypt item metadata, sealing its content key to the owner and the given readers.
+	Encrypt(EncryptCmd),
+	/// Seal the content key of an item to another reader, e.g. a viewer granted access.
+	Share(ShareCmd),
+	/// Decrypt item metadata with the envelope sealed to the local key.
+	Decrypt(DecryptCmd),
+}
+
+impl CvCryptoCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		match self {
+			Self::PublicKey(cmd) => cmd.run(),
+			Self::Encrypt(cmd) => cmd.run(),
+			Self::Share(cmd) => cmd.run(),
+			Self::Decrypt(cmd) => cmd.run(),
+		}
+	}
+}
+
+/// Account key of the local keystore the x25519 key is derived from.
+#[derive(Debug, StructOpt)]
+pub struct KeystoreKey {
+	/// Path of the keystore, e.g. `<base-path>/chains/<chain>/keystore`.
+	#[structopt(long, parse(from_os_str))]
+	pub keystore_path: PathBuf,
+
+	/// SS58 address of the sr25519 account key.
+	#[structopt(long)]
+	pub public: String,
+
+	/// Key type the key was inserted with.
+	#[structopt(long, default_value = "acco")]
+	pub key_type: String,
+
+	/// Password of the key, if it has one.
+	#[structopt(long)]
+	pub password: Option<String>,
+}
+
+impl KeystoreKey {
+	/// The x25519 secret of the key, a hash of its sr25519 secret so it needs no storage of
+	/// its own.
+	fn secret(&self) -> sc_cli::Result<StaticSecret> {
+		let public = sr25519::Public::from_ss58check(&self.public)
+			.map_err(|e| format!("Invalid address {}: {:?}", self.public, e))?;
+		let key_type = KeyTypeId::try_from(self.key_type.as_str())
+			.map_err(|_| format!("Invalid key type {}.", self.key_type))?;
+
+		// The local keystore keeps the secret URI of a key as a JSON string, in a file named
+		// after the key type and the public key.
+		let file_name = format!("{}{}", hex::encode(key_type.0), hex::encode(public.0));
+		let path = self.keystore_path.join(file_name);
+		let file = std::fs::read(&path)
+			.map_err(|e| format!("Unable to read key {}: {}", path.display(), e))?;
+		let suri: String = serde_json::from_slice(&file)
+			.map_err(|e| format!("Invalid key file {}: {}", path.display(), e))?;
+		let pair = sr25519::Pair::from_string(&suri, self.password.as_deref())
+			.map_err(|e| format!("Invalid key {}: {:?}", self.public, e))?;
+		if pair.public() != public {
+			return Err(format!("Key {} does not match its keystore file.", self.public).into())
+		}
+
+		Ok(StaticSecret::from(blake2_256(&[&b"scv:x25519"[..], &pair.to_raw_vec()[..]].concat())))
+	}
+}
+
+/// The `cv-crypto public-key` command.
+#[derive(Debug, StructOpt)]
+pub struct PublicKeyCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub key: KeystoreKey,
+}
+
+impl PublicKeyCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let public = PublicKey::from(&self.key.secret()?);
+		println!("0x{}", hex::encode(public.as_bytes()));
+		Ok(())
+	}
+}
+
+/// The `cv-crypto encrypt` command.
+#[derive(Debug, StructOpt)]
+pub struct EncryptCmd {
+	/// Path of the plaintext metadata.
+	#[structopt(parse(from_os_str))]
+	pub file: PathBuf,
+
+	/// Registered x25519 key of another reader, e.g. the subject of an item proposed to them.
+	#[structopt(long = "reader")]
+	pub readers: Vec<String>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub key: KeystoreKey,
+}
+
+impl EncryptCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let plaintext = std::fs::read(&self.file)?;
+		let mut readers = vec![PublicKey::from(&self.key.secret()?)];
+		for reader in &self.readers {
+			readers.push(parse_key(reader)?);
+		}
+
+		let content_key: [u8; 32] = rand::random();
+		let envelopes: Vec<_> = readers
+			.iter()
+			.map(|reader| {
+				json!({
+					"reader": format!("0x{}", hex::encode(reader.as_bytes())),
+					"envelope": format!("0x{}", hex::encode(seal(&content_key, reader))),
+				})
+			})
+			.collect();
+		let metadata = encrypt(&content_key, &plaintext);
+
+		println!(
+			"{:#}",
+			json!({ "metadata": format!("0x{}", hex::encode(metadata)), "envelopes": envelopes })
+		);
+		Ok(())
+	}
+}
+
+/// The `cv-crypto share` command.
+#[derive(Debug, StructOpt)]
+pub struct ShareCmd {
+	/// Envelope of the item sealed to the local key.
+	#[structopt(long)]
+	pub envelope: String,
+
+	/// Registered x25519 key of the reader to seal the content key to.
+	#[structopt(long)]
+	pub reader: String,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub key: KeystoreKey,
+}
+
+impl ShareCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let envelope = parse_hex("envelope", &self.envelope)?;
+		let content_key = open(&envelope, &self.key.secret()?)
+			.ok_or("The envelope is not sealed to the local key.")?;
+		let reader = parse_key(&self.reader)?;
+
+		println!("0x{}", hex::encode(seal(&content_key, &reader)));
+		Ok(())
+	}
+}
+
+/// The `cv-crypto decrypt` command.
+#[derive(Debug, StructOpt)]
+pub struct DecryptCmd {
+	/// Encrypted metadata of the item.
+	#[structopt(long)]
+	pub metadata: String,
+
+	/// Envelope of the item sealed to the local key.
+	#[structopt(long)]
+	pub envelope: String,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub key: KeystoreKey,
+}
+
+impl DecryptCmd {
+	/// Run the command, writing the plaintext to stdout.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let envelope = parse_hex("envelope", &self.envelope)?;
+		let content_key = open(&envelope, &self.key.secret()?)
+			.ok_or("The envelope is not sealed to the local key.")?;
+		let plaintext = decrypt(&content_key, &parse_hex("metadata", &self.metadata)?)
+			.ok_or("The metadata does not decrypt with the content key of the envelope.")?;
+
+		std::io::stdout().write_all(&plaintext)?;
+		Ok(())
+	}
+}
+
+/// Encrypts `plaintext` under `content_key` with a random nonce.
+pub fn encrypt(content_key: &[u8; 32], plaintext: &[u8]) -> Vec<u8> {
+	let nonce: [u8; NONCE_LENGTH] = rand::random();
+	let ciphertext = ChaCha20Poly1305::new(Key::from_slice(content_key))
+		.encrypt(Nonce::from_slice(&nonce), plaintext)
+		.expect("encrypting a buffer in memory cannot fail; qed");
+	[&nonce[..], &ciphertext[..]].concat()
+}
+
+/// Decrypts metadata encrypted with [`encrypt`], `None` if it does not authenticate.
+pub fn decrypt(content_key: &[u8; 32], metadata: &[u8]) -> Option<Vec<u8>> {
+	if metadata.len() < NONCE_LENGTH {
+		return None
+	}
+	let (nonce, ciphertext) = metadata.split_at(NONCE_LENGTH);
+	ChaCha20Poly1305::new(Key::from_slice(content_key))
+		.decrypt(Nonce::from_slice(nonce), ciphertext)
+		.ok()
+}
+
+/// Seals `content_key` to the x25519 key `reader`.
+pub fn seal(content_key: &[u8; 32], reader: &PublicKey) -> Vec<u8> {
+	let ephemeral = StaticSecret::from(rand::random::<[u8; 32]>());
+	let ephemeral_public = PublicKey::from(&ephemeral);
+	let cipher = envelope_cipher(&ephemeral.diffie_hellman(reader), &ephemeral_public, reader);
+	let sealed = cipher
+		.encrypt(Nonce::from_slice(&[0; NONCE_LENGTH]), &content_key[..])
+		.expect("encrypting a buffer in memory cannot fail; qed");
+	[&ephemeral_public.as_bytes()[..], &sealed[..]].concat()
+}
+
+/// Opens an envelope made with [`seal`] with the reader's `secret`.
+pub fn open(envelope: &[u8], secret: &StaticSecret) -> Option<[u8; 32]> {
+	if envelope.len() != ENVELOPE_LENGTH {
+		return None
+	}
+	let (ephemeral, sealed) = envelope.split_at(32);
+	let ephemeral = PublicKey::from(<[u8; 32]>::try_from(ephemeral).ok()?);
+	let cipher =
+		envelope_cipher(&secret.diffie_hellman(&ephemeral), &ephemeral, &PublicKey::from(secret));
+	let content_key = cipher.decrypt(Nonce::from_slice(&[0; NONCE_LENGTH]), sealed).ok()?;
+	content_key.try_into().ok()
+}
+
+/// Cipher of the envelope sealed with `ephemeral` to `reader`. Every envelope has its own
+/// ephemeral key, hence its own cipher key, so a fixed nonce is safe.
+fn envelope_cipher(
+	shared: &x25519_dalek::SharedSecret,
+	ephemeral: &PublicKey,
+	reader: &PublicKey,
+) -> ChaCha20Poly1305 {
+	let key = blake2_256(
+		&[
+			&b"scv:envelope"[..],
+			&shared.as_bytes()[..],
+			&ephemeral.as_bytes()[..],
+			&reader.as_bytes()[..],
+		]
+		.concat(),
+	);
+	ChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+fn parse_hex(name: &str, value: &str) -> sc_cli::Result<Vec<u8>> {
+	hex::decode(value.trim_start_matches("0x"))
+		.map_err(|e| format!("Invalid {} {}: {}", name, value, e).into())
+}
+
+/// Parses a hex-encoded x25519 public key.
+fn parse_key(value: &str) -> sc_cli::Result<PublicKey> {
+	let key = <[u8; 32]>::try_from(parse_hex("key", value)?)
+		.map_err(|_| format!("Invalid key {}: expected 32 bytes.", value))?;
+	Ok(PublicKey::from(key))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn reader(seed: u8) -> (StaticSecret, PublicKey) {
+		let secret = StaticSecret::from([seed; 32]);
+		let public = PublicKey::from(&secret);
+		(secret, public)
+	}
+
+	#[test]
+	fn sealed_content_key_should_open_with_reader_key_only() {
+		let (secret, public) = reader(1);
+		let (other_secret, _) = reader(2);
+		let content_key: [u8; 32] = rand::random();
+
+		let envelope = seal(&content_key, &public);
+		assert_eq!(envelope.len(), ENVELOPE_LENGTH);
+		assert_eq!(open(&envelope, &secret), Some(content_key));
+		assert_eq!(open(&envelope, &other_secret), None);
+		// Every envelope has its own ephemeral key.
+		assert_ne!(seal(&content_key, &public), envelope);
+	}
+
+	#[test]
+	fn tampered_envelope_should_not_open() {
+		let (secret, public) = reader(1);
+		let envelope = seal(&[9; 32], &public);
+
+		for index in [0, 31, 32, ENVELOPE_LENGTH - 1] {
+			let mut tampered = envelope.clone();
+			tampered[index] ^= 1;
+			assert_eq!(open(&tampered, &secret), None, "byte {}", index);
+		}
+		assert_eq!(open(&envelope[..ENVELOPE_LENGTH - 1], &secret), None);
+		assert_eq!(open(&[&envelope[..], &[0]].concat(), &secret), None);
+	}
+
+	#[test]
+	fn metadata_should_decrypt_with_its_content_key_only() {
+		let content_key: [u8; 32] = rand::random();
+		let plaintext = br#"{"employer":"ACME","salary":"confidential"}"#;
+
+		let metadata = encrypt(&content_key, plaintext);
+		assert_eq!(metadata.len(), NONCE_LENGTH + plaintext.len() + 16);
+		assert_eq!(decrypt(&content_key, &metadata), Some(plaintext.to_vec()));
+		assert_eq!(decrypt(&[0; 32], &metadata), None);
+		// Nonces are random, so the same plaintext encrypts differently.
+		assert_ne!(encrypt(&content_key, plaintext), metadata);
+
+		let mut tampered = metadata.clone();
+		*tampered.last_mut().unwrap() ^= 1;
+		assert_eq!(decrypt(&content_key, &tampered), None);
+		tampered = metadata.clone();
+		tampered[0] ^= 1;
+		assert_eq!(decrypt(&content_key, &tampered), None);
+		assert_eq!(decrypt(&content_key, &metadata[..NONCE_LENGTH - 1]), None);
+	}
+
+	#[test]
+	fn envelope_should_carry_content_key_end_to_end() {
+		let (owner_secret, owner) = reader(1);
+		let (viewer_secret, viewer) = reader(3);
+		let content_key: [u8; 32] = rand::random();
+		let metadata = encrypt(&content_key, b"BSc in Computer Science");
+
+		// The owner shares the key it opened from its own envelope, as `cv-crypto share` does.
+		let opened = open(&seal(&content_key, &owner), &owner_secret).unwrap();
+		let shared = seal(&opened, &viewer);
+		let viewer_key = open(&shared, &viewer_secret).unwrap();
+		assert_eq!(decrypt(&viewer_key, &metadata), Some(b"BSc in Computer Science".to_vec()));
+	}
+}
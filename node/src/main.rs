@@ -6,7 +6,10 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod cv_crypto;
+mod open_badge;
 mod rpc;
+mod verify_document;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
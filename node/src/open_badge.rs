@@ -0,0 +1,114 @@
+//! Open Badges 3.0 import and export subcommands.
+
+use std::{path::PathBuf, sync::Arc};
+
+use pallet_certificate::{Certificate, CertificateClass};
+use pallet_certificate_rpc::{open_badges, CertificateRuntimeApi};
+use pallet_utils::TypeID;
+use sc_cli::{CliConfiguration, DatabaseParams, SharedParams};
+use scv_node::{opaque::Block, AccountId, Hash, Runtime};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+use structopt::StructOpt;
+
+/// Convert between Open Badges 3.0 credentials and certificates.
+#[derive(Debug, StructOpt)]
+pub enum OpenBadgeCmd {
+	/// Print the `create_certificate` arguments for an Open Badges 3.0 credential.
+	Import(ImportCmd),
+	/// Print a certificate of the local chain as an Open Badges 3.0 credential.
+	Export(ExportCmd),
+}
+
+/// The `open-badge import` command.
+#[derive(Debug, StructOpt)]
+pub struct ImportCmd {
+	/// Path of the Open Badges 3.0 JSON credential.
+	#[structopt(parse(from_os_str))]
+	pub file: PathBuf,
+
+	/// Certificate class to issue the certificate under.
+	#[structopt(long)]
+	pub class_id: TypeID,
+}
+
+impl ImportCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let badge: serde_json::Value = serde_json::from_slice(&std::fs::read(&self.file)?)
+			.map_err(|e| format!("Invalid JSON in {}: {}", self.file.display(), e))?;
+		let badge = open_badges::import::<AccountId>(&badge).map_err(|e| e.to_string())?;
+		if badge.holder.is_none() {
+			eprintln!("The badge subject is not an SCV account, set `holder` before submitting.");
+		}
+
+		println!("{:#}", badge.to_json(self.class_id));
+		Ok(())
+	}
+}
+
+/// The `open-badge export` command.
+#[derive(Debug, StructOpt)]
+pub struct ExportCmd {
+	/// Id of the certificate, or its legacy sequential number.
+	#[structopt(long)]
+	pub cid: String,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportCmd {
+	/// Run the command against the best block of `client`.
+	pub fn run<C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+		C::Api: CertificateRuntimeApi<
+			Block,
+			AccountId,
+			Certificate<Runtime>,
+			CertificateClass<Runtime>,
+			Hash,
+		>,
+	{
+		let api = client.runtime_api();
+		let at = client.info().best_hash;
+		let block = BlockId::hash(at);
+		let query_error = |e| format!("Unable to query certificate: {:?}", e);
+
+		let cid = match self.cid.parse::<TypeID>() {
+			Ok(number) => api
+				.certificate_id(&block, number)
+				.map_err(query_error)?
+				.ok_or_else(|| format!("Certificate {} not found.", number))?,
+			Err(_) => self.cid.parse::<Hash>().map_err(|_| format!("Invalid id {}.", self.cid))?,
+		};
+		let certificate = api
+			.certificate(&block, cid)
+			.map_err(query_error)?
+			.ok_or_else(|| format!("Certificate {:?} not found.", cid))?;
+		let class = api
+			.class(&block, certificate.class_id)
+			.map_err(query_error)?
+			.ok_or_else(|| format!("Certificate class {} not found.", certificate.class_id))?;
+
+		println!("{:#}", open_badges::render(&certificate, &class, &at));
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -7,7 +7,7 @@
 
 use std::sync::Arc;
 
-use scv_node::{opaque::Block, AccountId, Balance, Index};
+use scv_node::{opaque::Block, AccountId, Balance, Hash, Index, Runtime};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -32,9 +32,17 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_certificate_rpc::CertificateRuntimeApi<
+		Block,
+		AccountId,
+		pallet_certificate::Certificate<Runtime>,
+		pallet_certificate::CertificateClass<Runtime>,
+		Hash,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use pallet_certificate_rpc::{CertificateApi, Certificates};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -45,6 +53,8 @@ where
 
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+	io.extend_with(CertificateApi::to_delegate(Certificates::<_, _, Runtime>::new(client.clone())));
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
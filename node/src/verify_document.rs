@@ -0,0 +1,63 @@
+//! The `verify-document` subcommand.
+
+use std::path::PathBuf;
+
+use jsonrpc_core_client::transports::http;
+use pallet_certificate::DocumentHash;
+use pallet_certificate_rpc::{document, gen_client::Client};
+use scv_node::{AccountId, Hash};
+use structopt::StructOpt;
+
+/// Check a local document against the certificates anchored on chain.
+#[derive(Debug, StructOpt)]
+pub struct VerifyDocumentCmd {
+	/// Path of the document, e.g. a diploma PDF.
+	#[structopt(parse(from_os_str))]
+	pub file: PathBuf,
+
+	/// Algorithm the issuer hashed the document with.
+	#[structopt(long, default_value = "blake2-256", possible_values = &document::ALGORITHMS)]
+	pub algorithm: String,
+
+	/// HTTP RPC endpoint of the node to query.
+	#[structopt(long, default_value = "http://127.0.0.1:9933")]
+	pub rpc_url: String,
+}
+
+impl VerifyDocumentCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let contents = std::fs::read(&self.file)?;
+		let digest = match document::hash(&self.algorithm, &contents) {
+			Some(DocumentHash::Blake2_256(digest)) | Some(DocumentHash::Sha2_256(digest)) => digest,
+			None => return Err(format!("Unknown hash algorithm {}.", self.algorithm).into()),
+		};
+
+		let result = tokio::runtime::Runtime::new()?
+			.block_on(async {
+				let client: Client<Hash, AccountId, Hash> = http::connect(&self.rpc_url).await?;
+				client.verify_document(self.algorithm.clone(), digest, None).await
+			})
+			.map_err(|e| format!("Request to {} failed: {}", self.rpc_url, e))?;
+
+		println!("Document     {} {:?}", self.algorithm, digest);
+		if result["anchored"] != true {
+			return Err("The document is not anchored by any unrevoked certificate.".into())
+		}
+		for certificate in result["certificates"].as_array().into_iter().flatten() {
+			let field = |key: &str| certificate[key].as_str().unwrap_or_default().to_owned();
+			println!();
+			println!("Certificate  {}", field("certificateId"));
+			println!("Issued by    {}", field("org"));
+			for co_issuer in certificate["coIssuers"].as_array().into_iter().flatten() {
+				println!("Co-issued by {}", co_issuer.as_str().unwrap_or_default());
+			}
+			println!("Holder       {}", field("holder"));
+			match certificate["reason"].as_str() {
+				Some(reason) => println!("Status       {} ({})", field("verdict"), reason),
+				None => println!("Status       {}", field("verdict")),
+			}
+		}
+		Ok(())
+	}
+}
@@ -28,6 +28,9 @@ pub mod pallet {
 		metadata: Vec<u8>,
 	}
 
+	/// An x25519 public key, which content keys of encrypted records are sealed to.
+	pub type EncryptionKey = [u8; 32];
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -54,6 +57,12 @@ pub mod pallet {
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
 	pub type AccountRole<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Role, OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn encryption_key)]
+	/// Key others encrypt data for an account to, e.g. the content key of a CV item.
+	pub type EncryptionKeys<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, EncryptionKey, OptionQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
@@ -61,6 +70,8 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		AccountRegisted,
 		AccountUpdated(T::AccountId),
+		/// An account set the key data is encrypted to for it. [account]
+		EncryptionKeySet(T::AccountId),
 	}
 
 	// Errors inform users that something went wrong.
@@ -120,5 +131,15 @@ pub mod pallet {
 				Err(_) => Err(Error::<T>::AccountNotRegistered)?,
 			}
 		}
+
+		/// Sets the x25519 key the caller receives encrypted data with, replacing any previous
+		/// one. Data sealed to the previous key has to be sealed again.
+		#[pallet::weight(10_000)]
+		pub fn set_encryption_key(origin: OriginFor<T>, key: EncryptionKey) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<EncryptionKeys<T>>::insert(&who, key);
+			Self::deposit_event(Event::EncryptionKeySet(who));
+			Ok(())
+		}
 	}
 }
@@ -0,0 +1,47 @@
+//! Runtime API definition for the certificate pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_certificate::{BatchLeafStatus, DocumentHash, StatusPurpose, Verdict};
+use pallet_utils::TypeID;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait CertificateApi<AccountId, Certificate, Class, Hash> where
+		AccountId: Codec,
+		Certificate: Codec,
+		Class: Codec,
+		Hash: Codec,
+	{
+		/// Returns the certificate stored under `cid`, if any.
+		fn certificate(cid: Hash) -> Option<Certificate>;
+
+		/// Returns the id of the certificate known by the legacy sequential `number`, if any.
+		fn certificate_id(number: TypeID) -> Option<Hash>;
+
+		/// Returns the certificate class `class_id`, if any.
+		fn class(class_id: TypeID) -> Option<Class>;
+
+		/// Returns the ids of the unrevoked certificates anchoring the document hash `document`.
+		fn certificates_by_document(document: DocumentHash) -> Vec<Hash>;
+
+		/// Tells whether certificate `cid` is valid for `holder` right now.
+		fn verify(cid: Hash, holder: AccountId) -> Verdict;
+
+		/// Returns the uncompressed `purpose` status list of `org`.
+		fn status_list(org: AccountId, purpose: StatusPurpose) -> Vec<u8>;
+
+		/// Checks a field revealed by the holder against the claims root of certificate `cid`.
+		fn verify_disclosure(
+			cid: Hash,
+			key: Vec<u8>,
+			value: Vec<u8>,
+			salt: Vec<u8>,
+			proof: Vec<Hash>,
+		) -> bool;
+
+		/// Checks the Merkle `proof` that `leaf` belongs to the anchored batch `batch_id`.
+		fn verify_batch_leaf(batch_id: TypeID, leaf: Hash, proof: Vec<Hash>) -> BatchLeafStatus;
+	}
+}
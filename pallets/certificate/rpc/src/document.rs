@@ -0,0 +1,29 @@
+//! Document hashes as exchanged over RPC, named after their multihash algorithms.
+
+use pallet_certificate::DocumentHash;
+use sp_core::{
+	hashing::{blake2_256, sha2_256},
+	H256,
+};
+
+/// Algorithm names accepted by [`parse`] and [`hash`].
+pub const ALGORITHMS: [&str; 2] = ["blake2-256", "sha2-256"];
+
+/// The `algorithm` `digest` of a document.
+pub fn parse(algorithm: &str, digest: H256) -> Option<DocumentHash> {
+	match algorithm {
+		"blake2-256" => Some(DocumentHash::Blake2_256(digest)),
+		"sha2-256" => Some(DocumentHash::Sha2_256(digest)),
+		_ => None,
+	}
+}
+
+/// Hashes `document` with `algorithm`.
+pub fn hash(algorithm: &str, document: &[u8]) -> Option<DocumentHash> {
+	let digest = match algorithm {
+		"blake2-256" => blake2_256(document),
+		"sha2-256" => sha2_256(document),
+		_ => return None,
+	};
+	parse(algorithm, H256(digest))
+}
@@ -0,0 +1,336 @@
+//! RPC interface for the certificate pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_certificate::{Certificate, CertificateClass, Verdict};
+use pallet_utils::TypeID;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::Ss58Codec, H256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_certificate_rpc_runtime_api::CertificateApi as CertificateRuntimeApi;
+
+pub mod document;
+pub mod open_badges;
+mod status_list;
+mod vc;
+
+#[cfg(test)]
+mod tests;
+
+/// A certificate id, or the sequential number certificates were known by before hash ids.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CertificateRef<Hash> {
+	Number(TypeID),
+	Id(Hash),
+}
+
+#[rpc]
+pub trait CertificateApi<BlockHash, AccountId, Hash> {
+	/// Renders the certificate `cid` as a W3C Verifiable Credential JSON-LD document.
+	#[rpc(name = "scv_exportVerifiableCredential")]
+	fn export_verifiable_credential(
+		&self,
+		cid: CertificateRef<Hash>,
+		at: Option<BlockHash>,
+	) -> Result<Value>;
+
+	/// Tells whether certificate `cid` is valid for `holder`.
+	#[rpc(name = "scv_verifyCertificate")]
+	fn verify_certificate(
+		&self,
+		cid: CertificateRef<Hash>,
+		holder: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Value>;
+
+	/// Returns the `purpose` ("revocation" or "suspension") status list of `org` as a
+	/// base64-encoded gzip bitstring, with the hash of the block it was read at.
+	#[rpc(name = "scv_statusList")]
+	fn status_list(
+		&self,
+		org: AccountId,
+		purpose: String,
+		at: Option<BlockHash>,
+	) -> Result<Value>;
+
+	/// Looks up the certificates anchoring the `algorithm` ("blake2-256" or "sha2-256") `digest`
+	/// of a document, one per issuing org, with their issuers and whether they are valid for
+	/// their holders.
+	#[rpc(name = "scv_verifyDocument")]
+	fn verify_document(
+		&self,
+		algorithm: String,
+		digest: H256,
+		at: Option<BlockHash>,
+	) -> Result<Value>;
+
+	/// Converts the Open Badges 3.0 credential `badge` into the arguments of
+	/// `create_certificate` for a certificate of class `class_id`, along with the org that has to
+	/// sign it.
+	#[rpc(name = "scv_importOpenBadge")]
+	fn import_open_badge(
+		&self,
+		badge: Value,
+		class_id: TypeID,
+		at: Option<BlockHash>,
+	) -> Result<Value>;
+
+	/// Renders the certificate `cid` as an Open Badges 3.0 credential.
+	#[rpc(name = "scv_exportOpenBadge")]
+	fn export_open_badge(&self, cid: CertificateRef<Hash>, at: Option<BlockHash>) -> Result<Value>;
+}
+
+/// A struct that implements the [`CertificateApi`].
+pub struct Certificates<C, Block, T> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, T)>,
+}
+
+impl<C, Block, T> Certificates<C, Block, T> {
+	/// Create new `Certificates` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The requested certificate does not exist.
+	NotFound,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::NotFound => 2,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query certificate.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+fn not_found(cid: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::NotFound.into()),
+		message: format!("Certificate {:?} not found.", cid),
+		data: None,
+	}
+}
+
+fn invalid_purpose(purpose: &str) -> RpcError {
+	RpcError::invalid_params(format!("Unknown status purpose {:?}.", purpose))
+}
+
+fn invalid_algorithm(algorithm: &str) -> RpcError {
+	RpcError::invalid_params(format!(
+		"Unknown hash algorithm {:?}, expected one of {:?}.",
+		algorithm,
+		document::ALGORITHMS
+	))
+}
+
+fn unknown_class(class_id: TypeID) -> RpcError {
+	RpcError::invalid_params(format!("Unknown certificate class {}.", class_id))
+}
+
+/// JSON representation of a [`Verdict`].
+fn verdict_json(verdict: Verdict) -> Value {
+	match verdict {
+		Verdict::Valid => json!({ "verdict": "valid" }),
+		Verdict::Revoked(reason) =>
+			json!({ "verdict": "revoked", "reason": String::from_utf8_lossy(&reason) }),
+		Verdict::Expired => json!({ "verdict": "expired" }),
+		Verdict::Suspended => json!({ "verdict": "suspended" }),
+		Verdict::Unknown => json!({ "verdict": "unknown" }),
+		Verdict::IssuerRevoked => json!({ "verdict": "issuerRevoked" }),
+		Verdict::Pending => json!({ "verdict": "pending" }),
+	}
+}
+
+impl<C, Block, T> Certificates<C, Block, T>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api:
+		CertificateRuntimeApi<Block, T::AccountId, Certificate<T>, CertificateClass<T>, T::Hash>,
+	T: pallet_certificate::Config,
+{
+	/// Resolves `cid` to a certificate id, looking legacy numbers up at block `at`.
+	fn resolve(&self, cid: CertificateRef<T::Hash>, at: &BlockId<Block>) -> Result<T::Hash> {
+		match cid {
+			CertificateRef::Id(cid) => Ok(cid),
+			CertificateRef::Number(number) => self
+				.client
+				.runtime_api()
+				.certificate_id(at, number)
+				.map_err(runtime_error)?
+				.ok_or_else(|| not_found(number)),
+		}
+	}
+}
+
+impl<C, Block, T> CertificateApi<<Block as BlockT>::Hash, T::AccountId, T::Hash>
+	for Certificates<C, Block, T>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api:
+		CertificateRuntimeApi<Block, T::AccountId, Certificate<T>, CertificateClass<T>, T::Hash>,
+	T: pallet_certificate::Config + Send + Sync + 'static,
+	T::AccountId: Ss58Codec,
+{
+	fn export_verifiable_credential(
+		&self,
+		cid: CertificateRef<T::Hash>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Value> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let cid = self.resolve(cid, &BlockId::hash(at))?;
+
+		let certificate = api
+			.certificate(&BlockId::hash(at), cid)
+			.map_err(runtime_error)?
+			.ok_or_else(|| not_found(cid))?;
+
+		Ok(vc::render(&certificate, &at))
+	}
+
+	fn verify_certificate(
+		&self,
+		cid: CertificateRef<T::Hash>,
+		holder: T::AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Value> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let cid = self.resolve(cid, &at)?;
+
+		let verdict = api.verify(&at, cid, holder).map_err(runtime_error)?;
+
+		Ok(verdict_json(verdict))
+	}
+
+	fn status_list(
+		&self,
+		org: T::AccountId,
+		purpose: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Value> {
+		let purpose =
+			status_list::parse_purpose(&purpose).ok_or_else(|| invalid_purpose(&purpose))?;
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let list = api.status_list(&BlockId::hash(at), org, purpose).map_err(runtime_error)?;
+		let encoded_list = status_list::encode(list).map_err(runtime_error)?;
+
+		Ok(json!({
+			"statusPurpose": status_list::purpose_name(purpose),
+			"encodedList": encoded_list,
+			"blockHash": at,
+		}))
+	}
+
+	fn verify_document(
+		&self,
+		algorithm: String,
+		digest: H256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Value> {
+		let document =
+			document::parse(&algorithm, digest).ok_or_else(|| invalid_algorithm(&algorithm))?;
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let cids = api.certificates_by_document(&at, document).map_err(runtime_error)?;
+		let mut certificates = Vec::with_capacity(cids.len());
+		for cid in cids {
+			let certificate = api
+				.certificate(&at, cid)
+				.map_err(runtime_error)?
+				.ok_or_else(|| not_found(cid))?;
+			let verdict =
+				api.verify(&at, cid, certificate.holder.clone()).map_err(runtime_error)?;
+
+			let mut entry = verdict_json(verdict);
+			entry["certificateId"] = json!(cid);
+			entry["org"] = json!(certificate.org.to_ss58check());
+			entry["coIssuers"] = certificate
+				.co_issuers
+				.iter()
+				.map(|c| json!(c.org.to_ss58check()))
+				.collect::<Vec<_>>()
+				.into();
+			entry["holder"] = json!(certificate.holder.to_ss58check());
+			certificates.push(entry);
+		}
+		Ok(json!({ "anchored": !certificates.is_empty(), "certificates": certificates }))
+	}
+
+	fn import_open_badge(
+		&self,
+		badge: Value,
+		class_id: TypeID,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Value> {
+		let badge = open_badges::import::<T::AccountId>(&badge)
+			.map_err(|e| RpcError::invalid_params(e.to_string()))?;
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let class = api
+			.class(&at, class_id)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_class(class_id))?;
+		if let Some(grade) = badge.grade {
+			if grade < class.min_grade || grade > class.max_grade {
+				return Err(RpcError::invalid_params(format!(
+					"Grade {} is outside of the bounds of class {}.",
+					grade, class_id
+				)))
+			}
+		}
+
+		let mut payload = badge.to_json(class_id);
+		payload["org"] = json!(class.org.to_ss58check());
+		Ok(payload)
+	}
+
+	fn export_open_badge(
+		&self,
+		cid: CertificateRef<T::Hash>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Value> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let cid = self.resolve(cid, &BlockId::hash(at))?;
+
+		let certificate = api
+			.certificate(&BlockId::hash(at), cid)
+			.map_err(runtime_error)?
+			.ok_or_else(|| not_found(cid))?;
+		let class = api
+			.class(&BlockId::hash(at), certificate.class_id)
+			.map_err(runtime_error)?
+			.ok_or_else(|| unknown_class(certificate.class_id))?;
+
+		Ok(open_badges::render(&certificate, &class, &at))
+	}
+}
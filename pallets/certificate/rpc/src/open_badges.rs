@@ -0,0 +1,222 @@
+//! Conversion between Open Badges 3.0 credentials and on-chain certificates.
+
+use crate::vc::{self, did, iso8601};
+use pallet_certificate::{Certificate, CertificateClass, ClassKind};
+use pallet_utils::{TypeID, UnixEpoch};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sp_core::{crypto::Ss58Codec, hashing::blake2_256, H256};
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// JSON-LD context of Open Badges 3.0. Versioned variants such as `context-3.0.3.json` are
+/// accepted on import.
+pub const CONTEXT: &str = "https://purl.imsglobal.org/spec/ob/v3p0/context.json";
+
+/// Why an Open Badge could not be imported.
+#[derive(Debug, PartialEq)]
+pub enum ImportError {
+	/// The document is not an Open Badges 3.0 credential.
+	NotOpenBadge,
+	/// A field required to build a certificate is missing.
+	MissingField(&'static str),
+	/// A date of the badge is not an ISO 8601 date-time.
+	InvalidDate(String),
+	/// The subject is an SCV DID whose account does not decode.
+	InvalidHolder(String),
+}
+
+impl std::fmt::Display for ImportError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::NotOpenBadge => write!(f, "Not an Open Badges 3.0 credential."),
+			Self::MissingField(field) => write!(f, "Missing field `{}`.", field),
+			Self::InvalidDate(date) => write!(f, "Invalid date {:?}.", date),
+			Self::InvalidHolder(id) => write!(f, "Invalid holder {:?}.", id),
+		}
+	}
+}
+
+/// The certificate terms an Open Badge translates to.
+pub struct ImportedBadge<AccountId> {
+	/// The subject's account, when the badge was issued to an SCV DID. Otherwise the caller has
+	/// to pick the holder.
+	pub holder: Option<AccountId>,
+	pub grade: Option<u32>,
+	/// Compact JSON summary of the badge, its achievement and its original issuer.
+	pub metadata: String,
+	pub expires: Option<UnixEpoch>,
+	/// Blake2-256 of the badge document, so the original can be checked against the certificate.
+	pub content_hash: H256,
+}
+
+impl<AccountId: Ss58Codec> ImportedBadge<AccountId> {
+	/// The arguments of `create_certificate` for this badge as a certificate of `class_id`.
+	pub fn to_json(&self, class_id: TypeID) -> Value {
+		json!({
+			"holder": self.holder.as_ref().map(|holder| holder.to_ss58check()),
+			"classId": class_id,
+			"grade": self.grade,
+			"metadata": self.metadata,
+			"expires": self.expires,
+			"content": { "contentType": "None" },
+			"contentHash": { "Blake2_256": self.content_hash },
+			"claimsRoot": Value::Null,
+		})
+	}
+}
+
+/// Reads the certificate terms of the Open Badges 3.0 credential `badge`.
+///
+/// The content hash is taken over the badge re-serialized with sorted keys, so it does not
+/// depend on the formatting of the original file.
+pub fn import<AccountId: Ss58Codec>(
+	badge: &Value,
+) -> Result<ImportedBadge<AccountId>, ImportError> {
+	let contexts = badge
+		.get("@context")
+		.and_then(Value::as_array)
+		.ok_or(ImportError::NotOpenBadge)?;
+	let types = badge.get("type").and_then(Value::as_array).ok_or(ImportError::NotOpenBadge)?;
+	let is_ob3_context = |context: &Value| {
+		context
+			.as_str()
+			.map_or(false, |c| c.starts_with("https://purl.imsglobal.org/spec/ob/v3p0/"))
+	};
+	let is_badge_type = |ty: &Value| {
+		matches!(ty.as_str(), Some("OpenBadgeCredential") | Some("AchievementCredential"))
+	};
+	if !contexts.iter().any(is_ob3_context) || !types.iter().any(is_badge_type) {
+		return Err(ImportError::NotOpenBadge)
+	}
+
+	let subject = badge
+		.get("credentialSubject")
+		.ok_or(ImportError::MissingField("credentialSubject"))?;
+	let achievement = subject
+		.get("achievement")
+		.ok_or(ImportError::MissingField("credentialSubject.achievement"))?;
+	let name = achievement
+		.get("name")
+		.and_then(Value::as_str)
+		.ok_or(ImportError::MissingField("credentialSubject.achievement.name"))?;
+
+	let holder = match subject.get("id").and_then(Value::as_str) {
+		Some(id) => match id.strip_prefix("did:scv:") {
+			Some(address) => Some(
+				AccountId::from_ss58check(address)
+					.map_err(|_| ImportError::InvalidHolder(id.into()))?,
+			),
+			None => None,
+		},
+		None => None,
+	};
+
+	// The first numeric result, e.g. `{ "type": ["Result"], "value": "87" }`, is the grade.
+	let grade = subject
+		.get("result")
+		.and_then(Value::as_array)
+		.into_iter()
+		.flatten()
+		.filter_map(|result| match result.get("value")? {
+			Value::Number(value) => value.as_u64(),
+			Value::String(value) => value.trim().parse().ok(),
+			_ => None,
+		})
+		.find_map(|value| u32::try_from(value).ok());
+
+	// VC 2.0 credentials say `validUntil`, VC 1.1 ones `expirationDate`.
+	let expires = match badge.get("validUntil").or_else(|| badge.get("expirationDate")) {
+		Some(Value::String(date)) => Some(
+			vc::unix_from_iso8601(date).ok_or_else(|| ImportError::InvalidDate(date.clone()))?,
+		),
+		_ => None,
+	};
+
+	let issuer = match badge.get("issuer") {
+		Some(Value::String(id)) => json!({ "id": id }),
+		Some(issuer) => json!({ "id": issuer.get("id"), "name": issuer.get("name") }),
+		None => return Err(ImportError::MissingField("issuer")),
+	};
+	let metadata = json!({
+		"openBadge": {
+			"id": badge.get("id"),
+			"issuer": issuer,
+			"achievement": {
+				"id": achievement.get("id"),
+				"name": name,
+				"description": achievement.get("description"),
+				"criteria": achievement.get("criteria").and_then(|c| c.get("narrative")),
+			},
+		},
+	});
+
+	let canonical = serde_json::to_vec(badge).expect("serializing a JSON value cannot fail; qed");
+
+	Ok(ImportedBadge {
+		holder,
+		grade,
+		metadata: metadata.to_string(),
+		expires,
+		content_hash: H256(blake2_256(&canonical)),
+	})
+}
+
+/// The Open Badges `achievementType` of certificates of `kind`.
+fn achievement_type(kind: &ClassKind) -> &'static str {
+	match kind {
+		ClassKind::Degree => "Degree",
+		ClassKind::Diploma => "Diploma",
+		ClassKind::License => "License",
+		ClassKind::Award => "Award",
+		ClassKind::Course => "Course",
+		ClassKind::Training => "LearningProgram",
+	}
+}
+
+/// Renders `certificate` of `class`, as read at block `at`, as an Open Badges 3.0 credential.
+pub fn render<T, Hash>(
+	certificate: &Certificate<T>,
+	class: &CertificateClass<T>,
+	at: &Hash,
+) -> Value
+where
+	T: pallet_certificate::Config,
+	T::AccountId: Ss58Codec,
+	Hash: Serialize,
+{
+	let name = String::from_utf8_lossy(&class.name);
+	let expiration_date = certificate.expires.map(|expires| iso8601(expires.saturating_mul(1000)));
+	let result = certificate.grade.map(|grade| {
+		json!([{
+			"type": ["Result"],
+			"value": grade.to_string(),
+		}])
+	});
+
+	json!({
+		"@context": ["https://www.w3.org/2018/credentials/v1", CONTEXT],
+		"id": format!("urn:scv:certificate:{:?}", certificate.cid),
+		"type": ["VerifiableCredential", "OpenBadgeCredential"],
+		"name": name,
+		"issuer": {
+			"id": did(&certificate.org),
+			"type": ["Profile"],
+		},
+		"issuanceDate": iso8601(certificate.created.time.unique_saturated_into()),
+		"expirationDate": expiration_date,
+		"credentialSubject": {
+			"id": did(&certificate.holder),
+			"type": ["AchievementSubject"],
+			"achievement": {
+				"id": format!("urn:scv:class:{}", class.class_id),
+				"type": ["Achievement"],
+				"achievementType": achievement_type(&class.kind),
+				"name": name,
+				"description": String::from_utf8_lossy(&certificate.metadata),
+				"criteria": { "id": format!("urn:scv:class:{}", class.class_id) },
+			},
+			"result": result,
+		},
+		"proof": vc::proof(certificate, at),
+	})
+}
@@ -0,0 +1,37 @@
+//! Encoding of org status lists as StatusList2021 `encodedList` payloads.
+
+use std::io::Write;
+
+use flate2::{write::GzEncoder, Compression};
+use pallet_certificate::StatusPurpose;
+
+/// Lists are padded to 16KB (131,072 entries) so a list does not reveal how many certificates
+/// its org has issued.
+pub const MIN_LIST_BYTES: usize = 16 * 1024;
+
+/// Parses the `statusPurpose` of a StatusList2021 credential.
+pub fn parse_purpose(purpose: &str) -> Option<StatusPurpose> {
+	match purpose {
+		"revocation" => Some(StatusPurpose::Revocation),
+		"suspension" => Some(StatusPurpose::Suspension),
+		_ => None,
+	}
+}
+
+/// The `statusPurpose` name of `purpose`.
+pub fn purpose_name(purpose: StatusPurpose) -> &'static str {
+	match purpose {
+		StatusPurpose::Revocation => "revocation",
+		StatusPurpose::Suspension => "suspension",
+	}
+}
+
+/// Pads the bitstring `list`, gzips it and encodes the result as base64.
+pub fn encode(mut list: Vec<u8>) -> std::io::Result<String> {
+	if list.len() < MIN_LIST_BYTES {
+		list.resize(MIN_LIST_BYTES, 0);
+	}
+	let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+	encoder.write_all(&list)?;
+	Ok(base64::encode(encoder.finish()?))
+}
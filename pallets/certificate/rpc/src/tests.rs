@@ -0,0 +1,265 @@
+//! Tests of the credential conversions, against the SCV runtime.
+
+use crate::{
+	open_badges::{self, ImportError},
+	vc::{self, did, iso8601, metadata_value, unix_from_iso8601},
+};
+use pallet_certificate::{Certificate, CertificateClass, CertificateStatus, ClassKind, CoIssuer};
+use pallet_utils::{Content, WhoAndWhen};
+use scv_node::{AccountId, Runtime};
+use serde_json::{json, Value};
+use sp_core::{crypto::Ss58Codec, hashing::blake2_256, H256};
+
+fn account(seed: u8) -> AccountId {
+	AccountId::from([seed; 32])
+}
+
+/// A certificate of `org` to `holder`, issued at block 7 on 2022-04-15T05:20:00Z.
+fn certificate(metadata: &[u8]) -> Certificate<Runtime> {
+	Certificate {
+		cid: H256::repeat_byte(0xab),
+		number: 3,
+		org: account(1),
+		holder: account(2),
+		class_id: 4,
+		grade: Some(87),
+		created: WhoAndWhen { account: account(1), block: 7, time: 1_650_000_000_000 },
+		score: 90,
+		metadata: metadata.to_vec(),
+		content: Content::None,
+		content_hash: None,
+		claims_root: None,
+		expires: None,
+		status: CertificateStatus::Active,
+		revocation: None,
+		token: None,
+		co_issuers: vec![CoIssuer { org: account(3), signed: None, revocation: None }],
+		cosign_deadline: None,
+		status_index: 12,
+		depositor: account(1),
+		deposit: 0,
+	}
+}
+
+#[test]
+fn iso8601_should_format_known_epochs() {
+	assert_eq!(iso8601(0), "1970-01-01T00:00:00Z");
+	// Milliseconds are dropped.
+	assert_eq!(iso8601(999), "1970-01-01T00:00:00Z");
+	assert_eq!(iso8601(1_582_979_696_000), "2020-02-29T12:34:56Z");
+	assert_eq!(iso8601(1_583_020_800_000), "2020-03-01T00:00:00Z");
+	// Past the 32-bit rollover, and across a century that is not a leap year.
+	assert_eq!(iso8601(2_147_483_648_000), "2038-01-19T03:14:08Z");
+	assert_eq!(iso8601(4_107_542_399_000), "2100-02-28T23:59:59Z");
+	assert_eq!(iso8601(4_107_542_400_000), "2100-03-01T00:00:00Z");
+}
+
+#[test]
+fn metadata_should_embed_json_text_or_hex() {
+	assert_eq!(metadata_value(br#"{"gpa":3.9}"#), json!({ "gpa": 3.9 }));
+	assert_eq!(metadata_value(b"BSc"), json!("BSc"));
+	assert_eq!(metadata_value(&[0xff, 0x00]), json!("0xff00"));
+	assert_eq!(metadata_value(b""), json!(""));
+}
+
+#[test]
+fn verifiable_credential_should_name_issuer_holder_and_proof() {
+	let certificate = certificate(br#"{"major":"CS"}"#);
+	let at = H256::repeat_byte(0xcd);
+	let credential = vc::render(&certificate, &at);
+
+	let issuer = format!("did:scv:{}", account(1).to_ss58check());
+	assert_eq!(did(&account(1)), issuer);
+	assert_eq!(credential["issuer"], json!(issuer));
+	assert_eq!(credential["coIssuers"], json!([did(&account(3))]));
+	assert_eq!(credential["id"], json!(format!("urn:scv:certificate:{:?}", certificate.cid)));
+	assert_eq!(credential["type"], json!(["VerifiableCredential", "ScvCertificate"]));
+	assert_eq!(credential["issuanceDate"], json!("2022-04-15T05:20:00Z"));
+
+	let subject = &credential["credentialSubject"];
+	assert_eq!(subject["id"], json!(did(&account(2))));
+	assert_eq!(subject["certificateId"], json!(certificate.cid));
+	assert_eq!(subject["certificateNumber"], json!(3));
+	assert_eq!(subject["grade"], json!(87));
+	assert_eq!(subject["metadata"], json!({ "major": "CS" }));
+	assert_eq!(subject["claimsRoot"], Value::Null);
+
+	let status = &credential["credentialStatus"];
+	let list = format!("urn:scv:status-list:{}:revocation", account(1).to_ss58check());
+	assert_eq!(status["id"], json!(format!("{}#12", list)));
+	assert_eq!(status["type"], json!("StatusList2021Entry"));
+	assert_eq!(status["statusListIndex"], json!("12"));
+	assert_eq!(status["statusListCredential"], json!(list));
+
+	let proof = &credential["proof"];
+	assert_eq!(proof["type"], json!("ScvChainInclusion"));
+	assert_eq!(proof["created"], credential["issuanceDate"]);
+	assert_eq!(proof["proofPurpose"], json!("assertionMethod"));
+	assert_eq!(proof["verificationMethod"], json!(issuer));
+	assert_eq!(proof["issuedAtBlock"], json!(7));
+	assert_eq!(proof["blockHash"], json!(at));
+	// The key of the certificate under `CertificateById`, which maps by identity.
+	let storage_key = proof["storageKey"].as_str().unwrap();
+	assert_eq!(storage_key.len(), 2 + 2 * (16 + 16 + 32));
+	assert!(storage_key.ends_with(&"ab".repeat(32)));
+}
+
+/// The Open Badges 3.0 credential in `fixtures/open_badge.json`.
+fn open_badge() -> Value {
+	serde_json::from_str(include_str!("../fixtures/open_badge.json")).unwrap()
+}
+
+fn import(badge: &Value) -> Result<open_badges::ImportedBadge<AccountId>, ImportError> {
+	open_badges::import::<AccountId>(badge)
+}
+
+#[test]
+fn unix_from_iso8601_should_parse_offsets_and_reject_bad_dates() {
+	assert_eq!(unix_from_iso8601("1970-01-01T00:00:00Z"), Some(0));
+	assert_eq!(unix_from_iso8601("2024-02-29T00:00:00Z"), Some(1_709_164_800));
+	assert_eq!(unix_from_iso8601("2024-02-29T00:00:00.250z"), Some(1_709_164_800));
+	assert_eq!(unix_from_iso8601("2030-06-30T02:00:00+02:00"), Some(1_909_008_000));
+	assert_eq!(unix_from_iso8601("2021-12-31T23:30:00-05:30"), Some(1_641_013_200));
+	assert_eq!(unix_from_iso8601("2038-01-19T03:14:08Z"), Some(2_147_483_648));
+
+	for date in [
+		"2023-02-29T00:00:00Z",
+		"2030-04-31T00:00:00Z",
+		"2030-13-01T00:00:00Z",
+		"2030-00-10T00:00:00Z",
+		"2030-06-30T24:00:00Z",
+		"2030-06-30T00:60:00Z",
+		"2030-06-30",
+		"2030-06-30T00:00:00",
+		"2030-06-30T00:00:00+0200",
+		"2030-06-30 00:00:00Z",
+		"2030/06/30T00:00:00Z",
+		"1969-12-31T23:59:59Z",
+		"",
+	] {
+		assert_eq!(unix_from_iso8601(date), None, "{}", date);
+	}
+}
+
+#[test]
+fn open_badge_should_import() {
+	let badge = open_badge();
+	let imported = import(&badge).unwrap();
+
+	// The subject is not an SCV DID, so the caller picks the holder.
+	assert_eq!(imported.holder, None);
+	assert_eq!(imported.grade, Some(87));
+	assert_eq!(imported.expires, Some(1_909_008_000));
+	assert_eq!(imported.content_hash, H256(blake2_256(&serde_json::to_vec(&badge).unwrap())));
+	let metadata: Value = serde_json::from_str(&imported.metadata).unwrap();
+	assert_eq!(
+		metadata,
+		json!({
+			"openBadge": {
+				"id": "urn:uuid:91537dba-56cb-11ec-bf63-0242ac130002",
+				"issuer": {
+					"id": "https://example.edu/issuers/565049",
+					"name": "Example University",
+				},
+				"achievement": {
+					"id": "https://example.edu/achievements/bsc-cs",
+					"name": "BSc in Computer Science",
+					"description": "Four-year undergraduate degree in computer science.",
+					"criteria": "Completed 240 ECTS credits, including a thesis.",
+				},
+			},
+		})
+	);
+
+	// The hash does not depend on how the file was formatted.
+	let compact = serde_json::to_string(&badge).unwrap();
+	let reformatted = import(&serde_json::from_str(&compact).unwrap()).unwrap();
+	assert_eq!(reformatted.content_hash, imported.content_hash);
+
+	let mut badge = badge;
+	badge["credentialSubject"]["id"] = json!(did(&account(2)));
+	assert_eq!(import(&badge).unwrap().holder, Some(account(2)));
+}
+
+#[test]
+fn open_badge_should_survive_export_and_import() {
+	let imported = import(&open_badge()).unwrap();
+	let mut certificate = certificate(imported.metadata.as_bytes());
+	certificate.grade = imported.grade;
+	certificate.expires = imported.expires;
+	let class = CertificateClass::<Runtime> {
+		class_id: 4,
+		org: account(1),
+		kind: ClassKind::Degree,
+		name: b"BSc in Computer Science".to_vec(),
+		min_grade: 0,
+		max_grade: 100,
+		created: certificate.created.clone(),
+	};
+
+	let exported = open_badges::render(&certificate, &class, &H256::repeat_byte(0xcd));
+	assert_eq!(exported["issuer"]["id"], json!(did(&account(1))));
+	assert_eq!(exported["expirationDate"], json!("2030-06-30T00:00:00Z"));
+	let achievement = &exported["credentialSubject"]["achievement"];
+	assert_eq!(achievement["achievementType"], json!("Degree"));
+	assert_eq!(exported["proof"]["type"], json!("ScvChainInclusion"));
+
+	let reimported = import(&exported).unwrap();
+	assert_eq!(reimported.holder, Some(account(2)));
+	assert_eq!(reimported.grade, imported.grade);
+	assert_eq!(reimported.expires, imported.expires);
+	let metadata: Value = serde_json::from_str(&reimported.metadata).unwrap();
+	assert_eq!(metadata["openBadge"]["issuer"]["id"], json!(did(&account(1))));
+	assert_eq!(metadata["openBadge"]["achievement"]["name"], json!("BSc in Computer Science"));
+}
+
+#[test]
+fn malformed_open_badges_should_be_rejected() {
+	let rejected = |change: fn(&mut Value)| {
+		let mut badge = open_badge();
+		change(&mut badge);
+		import(&badge).err()
+	};
+
+	assert_eq!(import(&json!("OpenBadgeCredential")).err(), Some(ImportError::NotOpenBadge));
+	assert_eq!(
+		rejected(|b| b["@context"] = json!(["https://www.w3.org/2018/credentials/v1"])),
+		Some(ImportError::NotOpenBadge)
+	);
+	assert_eq!(
+		rejected(|b| b["type"] = json!(["VerifiableCredential"])),
+		Some(ImportError::NotOpenBadge)
+	);
+	assert_eq!(
+		rejected(|b| b["credentialSubject"] = json!(null)),
+		Some(ImportError::MissingField("credentialSubject.achievement"))
+	);
+	assert_eq!(
+		rejected(|b| {
+			b.as_object_mut().unwrap().remove("credentialSubject");
+		}),
+		Some(ImportError::MissingField("credentialSubject"))
+	);
+	assert_eq!(
+		rejected(|b| b["credentialSubject"]["achievement"]["name"] = json!(7)),
+		Some(ImportError::MissingField("credentialSubject.achievement.name"))
+	);
+	assert_eq!(
+		rejected(|b| {
+			b.as_object_mut().unwrap().remove("issuer");
+		}),
+		Some(ImportError::MissingField("issuer"))
+	);
+	assert_eq!(
+		rejected(|b| b["credentialSubject"]["id"] = json!("did:scv:not-an-address")),
+		Some(ImportError::InvalidHolder("did:scv:not-an-address".into()))
+	);
+	assert_eq!(
+		rejected(|b| b["expirationDate"] = json!("2030-02-30T00:00:00Z")),
+		Some(ImportError::InvalidDate("2030-02-30T00:00:00Z".into()))
+	);
+	// A result that is not a number leaves the grade out rather than failing the import.
+	let mut badge = open_badge();
+	badge["credentialSubject"]["result"] = json!([{ "type": ["Result"], "value": "A+" }]);
+	assert_eq!(import(&badge).unwrap().grade, None);
+}
@@ -0,0 +1,169 @@
+//! Rendering of on-chain certificates as W3C Verifiable Credentials.
+
+use crate::status_list;
+use pallet_certificate::{Certificate, CertificateById, StatusPurpose};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sp_core::{crypto::Ss58Codec, Bytes};
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// The DID of an on-chain account.
+pub fn did<AccountId: Ss58Codec>(account: &AccountId) -> String {
+	format!("did:scv:{}", account.to_ss58check())
+}
+
+/// Renders `certificate`, as read at block `at`, as a JSON-LD credential.
+///
+/// The proof section does not carry a signature, see [`proof`].
+pub fn render<T, Hash>(certificate: &Certificate<T>, at: &Hash) -> Value
+where
+	T: pallet_certificate::Config,
+	T::AccountId: Ss58Codec,
+	Hash: Serialize,
+{
+	let issuer = did(&certificate.org);
+	let issuance_date = iso8601(certificate.created.time.unique_saturated_into());
+	let list_uri = format!(
+		"urn:scv:status-list:{}:{}",
+		certificate.org.to_ss58check(),
+		status_list::purpose_name(StatusPurpose::Revocation)
+	);
+
+	json!({
+		"@context": [
+			"https://www.w3.org/2018/credentials/v1",
+			"https://w3id.org/vc/status-list/2021/v1"
+		],
+		"id": format!("urn:scv:certificate:{:?}", certificate.cid),
+		"type": ["VerifiableCredential", "ScvCertificate"],
+		"issuer": issuer,
+		"coIssuers": certificate.co_issuers.iter().map(|c| did(&c.org)).collect::<Vec<_>>(),
+		"issuanceDate": issuance_date,
+		"credentialSubject": {
+			"id": did(&certificate.holder),
+			"certificateId": certificate.cid,
+			"certificateNumber": certificate.number,
+			"classId": certificate.class_id,
+			"grade": certificate.grade,
+			"score": certificate.score,
+			"metadata": metadata_value(&certificate.metadata),
+			"claimsRoot": certificate.claims_root,
+		},
+		"credentialStatus": {
+			"id": format!("{}#{}", list_uri, certificate.status_index),
+			"type": "StatusList2021Entry",
+			"statusPurpose": status_list::purpose_name(StatusPurpose::Revocation),
+			"statusListIndex": certificate.status_index.to_string(),
+			"statusListCredential": list_uri,
+		},
+		"proof": proof(certificate, at),
+	})
+}
+
+/// A proof pointing at the storage entry backing `certificate` as read at block `at`, so a
+/// verifier can check its inclusion with `state_getReadProof`.
+pub fn proof<T, Hash>(certificate: &Certificate<T>, at: &Hash) -> Value
+where
+	T: pallet_certificate::Config,
+	T::AccountId: Ss58Codec,
+	Hash: Serialize,
+{
+	let issued_at_block: u64 = certificate.created.block.unique_saturated_into();
+	let storage_key = CertificateById::<T>::hashed_key_for(&certificate.cid);
+
+	json!({
+		"type": "ScvChainInclusion",
+		"created": iso8601(certificate.created.time.unique_saturated_into()),
+		"proofPurpose": "assertionMethod",
+		"verificationMethod": did(&certificate.org),
+		"issuedAtBlock": issued_at_block,
+		"blockHash": at,
+		"storageKey": Bytes(storage_key),
+	})
+}
+
+/// Certificate metadata is embedded as JSON when it parses as such, as a plain string when it is
+/// UTF-8 and as hex otherwise.
+pub fn metadata_value(metadata: &[u8]) -> Value {
+	match std::str::from_utf8(metadata) {
+		Ok(text) => serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.into())),
+		Err(_) => json!(Bytes(metadata.to_vec())),
+	}
+}
+
+/// Formats milliseconds since the unix epoch as an ISO 8601 UTC date-time.
+pub fn iso8601(millis: u64) -> String {
+	let secs = millis / 1000;
+	let (hour, minute, second) = (secs % 86_400 / 3_600, secs % 3_600 / 60, secs % 60);
+
+	// Civil date from days since 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+	let z = (secs / 86_400) as i64 + 719_468;
+	let era = z.div_euclid(146_097);
+	let doe = z.rem_euclid(146_097);
+	let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = doy - (153 * mp + 2) / 5 + 1;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+	format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, hour, minute, second)
+}
+
+/// Parses an ISO 8601 date-time such as `2024-06-30T00:00:00Z` or `2024-06-30T02:00:00+02:00`
+/// into seconds since the unix epoch. Fractional seconds are dropped, and days the month does not
+/// have, such as February 30th, are rejected.
+pub fn unix_from_iso8601(date: &str) -> Option<u64> {
+	let number = |range: std::ops::Range<usize>| date.get(range)?.parse::<i64>().ok();
+	let separators = (date.get(4..5)?, date.get(7..8)?, date.get(10..11)?);
+	if !matches!(separators, ("-", "-", "T" | "t")) {
+		return None
+	}
+	let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+	let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+	let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+	let days_in_month = match month {
+		2 if leap_year => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	};
+	if !(1..=12).contains(&month) || !(1..=days_in_month).contains(&day) {
+		return None
+	}
+	if hour > 23 || minute > 59 || second > 60 {
+		return None
+	}
+
+	let mut zone = date.get(19..)?;
+	if let Some(fraction) = zone.strip_prefix('.') {
+		zone = fraction.trim_start_matches(|c: char| c.is_ascii_digit());
+	}
+	let offset = match zone {
+		"Z" | "z" => 0,
+		_ => {
+			let sign = match zone.get(0..1)? {
+				"+" => 1,
+				"-" => -1,
+				_ => return None,
+			};
+			if zone.len() != 6 || zone.get(3..4)? != ":" {
+				return None
+			}
+			let hours = zone.get(1..3)?.parse::<i64>().ok()?;
+			let minutes = zone.get(4..6)?.parse::<i64>().ok()?;
+			sign * (hours * 3_600 + minutes * 60)
+		},
+	};
+
+	// Days since 1970-01-01 from a civil date, the inverse of the algorithm in `iso8601`.
+	let y = if month <= 2 { year - 1 } else { year };
+	let era = y.div_euclid(400);
+	let yoe = y - era * 400;
+	let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+	let days = era * 146_097 + doe - 719_468;
+
+	let secs = days * 86_400 + hour * 3_600 + minute * 60 + second - offset;
+	u64::try_from(secs).ok()
+}
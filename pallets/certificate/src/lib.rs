@@ -5,6 +5,10 @@
 /// <https://docs.substrate.io/v3/runtime/frame>
 pub use pallet::*;
 
+pub mod merkle;
+pub mod migrations;
+pub mod scoring;
+
 #[cfg(test)]
 mod mock;
 
@@ -16,30 +20,316 @@ mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			tokens::nonfungibles::{Create, Inspect, Mutate},
+			BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency, StorageVersion,
+		},
+		transactional,
+	};
 	use frame_system::pallet_prelude::*;
-	use pallet_utils::{Role, Status, TypeID, String};
+	use pallet_utils::{
+		ApprovalStatus, BalanceOf, Content, Role, Status, String, TypeID, UnixEpoch, WhoAndWhen,
+	};
+	use sp_core::{ed25519, sr25519, H256};
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		MultiSignature,
+	};
 	use scale_info::TypeInfo;
 	use frame_support::inherent::Vec;
+	use crate::scoring::{ScoreContext, ScoringPolicy};
 
 	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 	#[scale_info(bounds(), skip_type_params(T))]
 	pub struct Certificate<T:Config> {
-		cid: TypeID,
-		org: T::AccountId,
-		scrore: u32,
-		metadata: String,
+		pub cid: T::Hash,
+		/// Sequential number the certificate was known by before hash-based ids.
+		pub number: TypeID,
+		pub org: T::AccountId,
+		pub holder: T::AccountId,
+		pub class_id: TypeID,
+		pub grade: Option<u32>,
+		pub created: WhoAndWhen<T>,
+		pub score: u32,
+		pub metadata: String,
+		/// Off-chain document backing the certificate, e.g. the diploma PDF.
+		pub content: Content,
+		/// Hash of the official document, so a verifier holding a copy can check it is genuine.
+		pub content_hash: Option<DocumentHash>,
+		/// Root of the salted per-field commitments, see [`crate::merkle::claim_leaf`]. Holders
+		/// keep the salts off-chain and reveal single fields with an inclusion proof. Certificates
+		/// with a claims root have empty `metadata`.
+		pub claims_root: Option<T::Hash>,
+		pub expires: Option<UnixEpoch>,
+		pub status: CertificateStatus,
+		pub revocation: Option<Revocation<T>>,
+		/// Soulbound token mirroring the certificate, as `(collection, item)`.
+		pub token: Option<(TypeID, TypeID)>,
+		/// Other orgs standing behind a joint certificate.
+		pub co_issuers: Vec<CoIssuer<T>>,
+		/// Co-issuers must sign a joint certificate before this time.
+		pub cosign_deadline: Option<UnixEpoch>,
+		/// Position of the certificate in the status lists of its issuer.
+		pub status_index: u32,
+		/// Account the storage deposit was reserved from: the issuer, or the holder of a claimed
+		/// certificate.
+		pub depositor: T::AccountId,
+		/// Storage deposit still reserved from the depositor. Revocation refunds all of it but the
+		/// base deposit, which stays reserved for the record kept for verifiers.
+		pub deposit: BalanceOf<T>,
+	}
+
+	impl<T: Config> Certificate<T> {
+		/// Orgs standing behind the certificate, its proposer first.
+		pub fn issuers(&self) -> impl Iterator<Item = &T::AccountId> {
+			core::iter::once(&self.org).chain(self.co_issuers.iter().map(|c| &c.org))
+		}
+
+		/// Revocations recorded by the issuers, its proposer first.
+		pub fn revocations(&self) -> impl Iterator<Item = &Revocation<T>> {
+			self.revocation
+				.iter()
+				.chain(self.co_issuers.iter().filter_map(|c| c.revocation.as_ref()))
+		}
+	}
+
+	/// An org co-issuing a joint certificate with its proposer.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct CoIssuer<T: Config> {
+		pub org: T::AccountId,
+		/// When the org co-signed the certificate, if it has.
+		pub signed: Option<WhoAndWhen<T>>,
+		/// The org's own revocation of the certificate, if any.
+		pub revocation: Option<Revocation<T>>,
+	}
+
+	/// Fields of a certificate chosen by its issuer.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct CertificateTerms<T: Config> {
+		pub holder: T::AccountId,
+		pub class_id: TypeID,
+		pub grade: Option<u32>,
+		pub metadata: String,
+		pub expires: Option<UnixEpoch>,
+		pub content: Content,
+		pub content_hash: Option<DocumentHash>,
+		pub claims_root: Option<T::Hash>,
+	}
+
+	/// A holder's request for a certificate of one of an org's classes.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct CertificateRequest<T: Config> {
+		pub request_id: TypeID,
+		pub holder: T::AccountId,
+		pub org: T::AccountId,
+		pub class_id: TypeID,
+		/// Supporting documents, e.g. a transcript or an employment contract.
+		pub evidence: Content,
+		pub created: WhoAndWhen<T>,
+		/// The org can no longer approve the request after this time.
+		pub expires: UnixEpoch,
+		pub status: ApprovalStatus,
+		/// Reason given by the org when rejecting the request.
+		pub reason: Option<String>,
+		/// Issuance fee reserved from the holder, paid to the org on approval.
+		pub fee: BalanceOf<T>,
+		/// Deposit reserved from the holder while the request is pending.
+		pub deposit: BalanceOf<T>,
+		/// Certificate issued when the request was approved.
+		pub cid: Option<T::Hash>,
+	}
+
+	/// Certificate terms signed off-chain by an org, for the holder to claim on-chain.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct CertificatePayload<T: Config> {
+		pub org: T::AccountId,
+		pub terms: CertificateTerms<T>,
+		/// Chosen by the org; each nonce can be claimed once.
+		pub nonce: u64,
+		/// The payload can no longer be claimed after this time.
+		pub valid_until: UnixEpoch,
+	}
+
+	/// Public key an org signs certificate payloads with.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum SigningKey {
+		Sr25519(sr25519::Public),
+		Ed25519(ed25519::Public),
+	}
+
+	impl SigningKey {
+		/// Checks `signature` over `message` against this key.
+		pub fn verify(&self, signature: &MultiSignature, message: &[u8]) -> bool {
+			match (self, signature) {
+				(Self::Sr25519(key), MultiSignature::Sr25519(sig)) =>
+					sp_io::crypto::sr25519_verify(sig, message, key),
+				(Self::Ed25519(key), MultiSignature::Ed25519(sig)) =>
+					sp_io::crypto::ed25519_verify(sig, message, key),
+				_ => false,
+			}
+		}
+	}
+
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum ClassKind {
+		Degree,
+		Diploma,
+		License,
+		Award,
+		Course,
+		Training,
+	}
+
+	/// A kind of certificate an org issues, e.g. "BSc in Computer Science".
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct CertificateClass<T: Config> {
+		pub class_id: TypeID,
+		pub org: T::AccountId,
+		pub kind: ClassKind,
+		pub name: String,
+		/// Bounds of the grades the org may award for this class.
+		pub min_grade: u32,
+		pub max_grade: u32,
+		pub created: WhoAndWhen<T>,
+	}
+
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum CertificateStatus {
+		/// A joint certificate still awaiting co-signatures.
+		Pending,
+		Active,
+		Suspended,
+		Revoked,
+	}
+
+	impl Default for CertificateStatus {
+		fn default() -> Self {
+			Self::Active
+		}
+	}
+
+	/// Digest of an official document, e.g. a diploma PDF, tagged with the algorithm that
+	/// produced it.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[allow(non_camel_case_types)]
+	pub enum DocumentHash {
+		Blake2_256(H256),
+		Sha2_256(H256),
+	}
+
+	/// What the bits of an org's status list record.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum StatusPurpose {
+		Revocation,
+		Suspension,
+	}
+
+	/// Answer to "is certificate X valid for holder Y right now?".
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum Verdict {
+		/// The certificate is active and held by the given account.
+		Valid,
+		/// The certificate was revoked by its issuer, with the given reason.
+		Revoked(String),
+		/// The certificate is past its expiry date.
+		Expired,
+		/// The certificate is temporarily suspended by its issuer.
+		Suspended,
+		/// No certificate with the given id is held by the given account.
+		Unknown,
+		/// The organization that issued the certificate has been revoked.
+		IssuerRevoked,
+		/// The joint certificate has not been co-signed by all its issuers yet.
+		Pending,
+	}
+
+	/// A Merkle root anchoring a batch of certificate hashes issued by one org.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct CertificateBatch<T: Config> {
+		pub batch_id: TypeID,
+		pub org: T::AccountId,
+		pub root: T::Hash,
+		pub leaf_count: u32,
+		pub created: WhoAndWhen<T>,
+		pub metadata: String,
+	}
+
+	/// Who revoked a certificate, when, and why.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct Revocation<T: Config> {
+		pub revoked: WhoAndWhen<T>,
+		pub reason: String,
+	}
+
+	/// Outcome of checking a certificate hash against an anchored batch.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum BatchLeafStatus {
+		/// The leaf is included in the batch and has not been revoked.
+		Valid,
+		/// The leaf is included in the batch but has been revoked by the issuer.
+		Revoked,
+		/// The proof does not link the leaf to the batch root.
+		NotIncluded,
+		/// No batch exists with the given id.
+		UnknownBatch,
 	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + pallet_utils::Config + pallet_sys_man::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Decides the score of the certificates being issued.
+		type ScoringPolicy: ScoringPolicy;
+		/// Non-fungible tokens mirroring certificates in NFT-aware wallets. Each org gets its own
+		/// collection. The runtime must prevent these tokens from being transferred.
+		type Nfts: Inspect<Self::AccountId, ClassId = TypeID, InstanceId = TypeID>
+			+ Create<Self::AccountId>
+			+ Mutate<Self::AccountId>;
+		/// Base deposit reserved from the issuer of a certificate, kept reserved after revocation.
+		#[pallet::constant]
+		type CertificateDepositBase: Get<BalanceOf<Self>>;
+		/// Deposit reserved per byte of certificate metadata.
+		#[pallet::constant]
+		type CertificateDepositPerByte: Get<BalanceOf<Self>>;
+		/// Seconds an org has to answer a certificate request.
+		#[pallet::constant]
+		type RequestTimeout: Get<UnixEpoch>;
+		/// Deposit reserved from a holder for each pending request, on top of the issuance fee.
+		#[pallet::constant]
+		type RequestDeposit: Get<BalanceOf<Self>>;
+		/// Maximum number of requests awaiting an answer from one org.
+		#[pallet::constant]
+		type MaxPendingRequests: Get<u32>;
+		/// Notified when a certificate gets revoked, e.g. to invalidate CV items backed by it.
+		type OnRevoke: OnCertificateRevoked<Self>;
+	}
+
+	/// Hook for pallets holding records backed by certificates.
+	pub trait OnCertificateRevoked<T: Config> {
+		/// Called once `certificate` is revoked by the first of its issuers.
+		fn on_certificate_revoked(certificate: &Certificate<T>);
+	}
+
+	impl<T: Config> OnCertificateRevoked<T> for () {
+		fn on_certificate_revoked(_certificate: &Certificate<T>) {}
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
@@ -48,14 +338,142 @@ pub mod pallet {
 	#[pallet::getter(fn certificate_id)]
 	// Learn more about declaring storage items:
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
+	/// Number of the next certificate, used as the nonce of its id.
 	pub type CertificateId<T> = StorageValue<_, TypeID, ValueQuery>;
 
-
 	#[pallet::storage]
 	#[pallet::getter(fn certificate_by_id)]
 	// Learn more about declaring storage items:
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
-	pub type CertificateById<T> = StorageMap<_, Twox64Concat, TypeID, Certificate<T>, OptionQuery>;
+	pub type CertificateById<T> = StorageMap<_, Identity, T::Hash, Certificate<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn certificate_id_by_number)]
+	/// Id of a certificate by its sequential number, for lookups by legacy numeric id.
+	pub type CertificateIdByNumber<T: Config> =
+		StorageMap<_, Twox64Concat, TypeID, T::Hash, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn certificate_by_document)]
+	/// Id of the certificate an org anchored a document hash with. Each org anchors a document at
+	/// most once, and the anchor is released when its certificate is revoked.
+	pub type CertificateIdByDocument<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		DocumentHash,
+		Twox64Concat,
+		T::AccountId,
+		T::Hash,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_status_index)]
+	/// Status list index the next certificate issued by an org gets.
+	pub type NextStatusIndex<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn status_list)]
+	/// Bitstrings of an org, one bit per status index, most significant bit first.
+	pub type StatusLists<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		StatusPurpose,
+		Vec<u8>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn issuance_fee)]
+	/// Fee an org charges holders requesting one of its certificates.
+	pub type IssuanceFeeByOrg<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn request_id)]
+	pub type RequestId<T> = StorageValue<_, TypeID, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn request_by_id)]
+	pub type RequestById<T> =
+		StorageMap<_, Twox64Concat, TypeID, CertificateRequest<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_requests)]
+	/// Requests awaiting an answer from an org, oldest first.
+	pub type PendingRequestsByOrg<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Vec<TypeID>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn signing_key)]
+	/// Keys an org signs certificate payloads with, and when they were added.
+	pub type SigningKeys<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		SigningKey,
+		WhoAndWhen<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn claim_nonce_used)]
+	/// Payload nonces already claimed, per org.
+	pub type UsedClaimNonces<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, u64, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_collection_id)]
+	pub type NextCollectionId<T> = StorageValue<_, TypeID, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collection_by_org)]
+	/// NFT collection holding the certificate tokens of an org.
+	pub type CollectionByOrg<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, TypeID, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_token_id)]
+	pub type NextTokenId<T> = StorageMap<_, Twox64Concat, TypeID, TypeID, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn certificate_by_token)]
+	/// Certificate mirrored by a token, keyed by collection and item.
+	pub type CertificateByToken<T> =
+		StorageDoubleMap<_, Twox64Concat, TypeID, Twox64Concat, TypeID, T::Hash, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn class_id)]
+	pub type ClassId<T> = StorageValue<_, TypeID, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn class_by_id)]
+	pub type ClassById<T> = StorageMap<_, Twox64Concat, TypeID, CertificateClass<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn batch_id)]
+	pub type BatchId<T> = StorageValue<_, TypeID, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn batch_by_id)]
+	pub type BatchById<T> = StorageMap<_, Twox64Concat, TypeID, CertificateBatch<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn revoked_batch_leaf)]
+	/// Leaves of a batch revoked by its issuer, keyed by batch id and leaf hash.
+	pub type RevokedBatchLeaves<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		TypeID,
+		Blake2_128Concat,
+		T::Hash,
+		Revocation<T>,
+		OptionQuery,
+	>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
@@ -66,6 +484,41 @@ pub mod pallet {
 		/// parameters. [something, who]
 		CertificateCreated(T::AccountId),
         CertificateRevoked(T::AccountId),
+		/// A holder requested a certificate from an org. [holder, org, request_id]
+		CertificateRequested(T::AccountId, T::AccountId, TypeID),
+		/// An org approved a request and issued the certificate. [request_id, cid]
+		RequestApproved(TypeID, T::Hash),
+		/// An org rejected a request. [request_id]
+		RequestRejected(TypeID),
+		/// A request was withdrawn by its holder or dropped after timing out. [request_id]
+		RequestCancelled(TypeID),
+		/// An org proposed a joint certificate. [org, cid]
+		JointCertificateProposed(T::AccountId, T::Hash),
+		/// A co-issuer signed a joint certificate. [org, cid]
+		CertificateCosigned(T::AccountId, T::Hash),
+		/// All co-issuers signed and the joint certificate became active. [cid]
+		CertificateActivated(T::Hash),
+		/// A pending joint certificate was withdrawn by its proposer or dropped after its
+		/// co-signing deadline. [cid]
+		JointCertificateCancelled(T::Hash),
+		/// A holder claimed a certificate signed by an org. [holder, cid]
+		CertificateClaimed(T::AccountId, T::Hash),
+		/// An org registered a payload signing key. [org, key]
+		SigningKeyAdded(T::AccountId, SigningKey),
+		/// An org removed a payload signing key. [org, key]
+		SigningKeyRemoved(T::AccountId, SigningKey),
+		/// An org set or cleared its issuance fee. [org, fee]
+		IssuanceFeeSet(T::AccountId, Option<BalanceOf<T>>),
+		/// A certificate class was created. [org, class_id]
+		ClassCreated(T::AccountId, TypeID),
+		/// A certificate was suspended by its issuer. [cid]
+		CertificateSuspended(T::Hash),
+		/// A suspended certificate was reinstated by its issuer. [cid]
+		CertificateReinstated(T::Hash),
+		/// A batch of certificates was anchored. [org, batch_id]
+		BatchAnchored(T::AccountId, TypeID),
+		/// A leaf of a certificate batch was revoked. [batch_id, leaf]
+		BatchLeafRevoked(TypeID, T::Hash),
 	}
 
 	// Errors inform users that something went wrong.
@@ -75,6 +528,74 @@ pub mod pallet {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// Only organizations approved by a system manager can issue certificates.
+		NotAnOrganization,
+		/// Certificate does not exist.
+		CertificateNotFound,
+		/// Only the org that issued a certificate can manage it.
+		NotCertificateIssuer,
+		/// Certificate has already been revoked.
+		CertificateAlreadyRevoked,
+		/// Certificate is not in a state that allows this operation.
+		InvalidCertificateStatus,
+		/// Expiry date must lie in the future.
+		InvalidExpiry,
+		/// Certificate class does not exist.
+		ClassNotFound,
+		/// Orgs can only issue certificates of their own classes.
+		NotClassOwner,
+		/// Minimum grade is above maximum grade.
+		InvalidGradeBounds,
+		/// Grade lies outside the bounds of the certificate class.
+		GradeOutOfBounds,
+		/// A batch must cover at least one certificate.
+		EmptyBatch,
+		/// Batch does not exist.
+		BatchNotFound,
+		/// Only the org that anchored a batch can manage it.
+		NotBatchIssuer,
+		/// Leaf has already been revoked.
+		LeafAlreadyRevoked,
+		/// Signing key is already registered for this org.
+		SigningKeyAlreadyAdded,
+		/// Signing key is not registered for this org.
+		SigningKeyNotFound,
+		/// Only the holder named in a payload can claim it.
+		NotCertificateHolder,
+		/// Payload is past its `valid_until` time.
+		ClaimExpired,
+		/// Signature does not match the payload and signing key.
+		InvalidSignature,
+		/// Payload nonce has already been claimed.
+		ClaimNonceUsed,
+		/// Request does not exist.
+		RequestNotFound,
+		/// Only the org a request was sent to can answer it.
+		NotRequestRecipient,
+		/// Request has already been answered.
+		RequestNotPending,
+		/// Request timed out before the org answered it.
+		RequestExpired,
+		/// Only the holder can cancel a request before it times out.
+		RequestNotExpired,
+		/// Org has too many requests awaiting an answer.
+		TooManyPendingRequests,
+		/// Orgs cannot request certificates from themselves.
+		SelfRequest,
+		/// Co-issuers must be distinct orgs other than the proposer.
+		InvalidCoIssuers,
+		/// Co-signing deadline must lie in the future.
+		InvalidCosignDeadline,
+		/// Co-signing deadline of the joint certificate has passed.
+		CosignDeadlinePassed,
+		/// Co-issuer has already signed the certificate.
+		AlreadyCosigned,
+		/// Only the proposer can cancel a joint certificate before its co-signing deadline.
+		CosignDeadlineNotPassed,
+		/// Document hash is already anchored by another certificate of this org.
+		DocumentAlreadyAnchored,
+		/// Certificates committing to their fields with a claims root carry no plaintext metadata.
+		MetadataWithClaimsRoot,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -85,36 +606,849 @@ pub mod pallet {
 		/// An example dispatchable that takes a singles value as a parameter, writes the value to
 		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
 		#[pallet::weight(10_000)]
-		pub fn create_certificate(origin: OriginFor<T>, _meta_data: String) -> DispatchResult {
+		#[transactional]
+		pub fn create_certificate(
+			origin: OriginFor<T>,
+			holder: T::AccountId,
+			class_id: TypeID,
+			grade: Option<u32>,
+			_meta_data: String,
+			expires: Option<UnixEpoch>,
+			content: Content,
+			content_hash: Option<DocumentHash>,
+			claims_root: Option<T::Hash>,
+		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
-			let cid = <CertificateId<T>>::get();
-			// Update storage.
-			<CertificateById<T>>::insert(cid, Certificate {
-				cid: cid,
-				org: who.clone(),
+			let terms = CertificateTerms {
+				holder,
+				class_id,
+				grade,
 				metadata: _meta_data,
-				scrore: 5,
+				expires,
+				content,
+				content_hash,
+				claims_root,
+			};
+			Self::issue(&who, &who, terms, Vec::new(), None)?;
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+
+		/// Stores a certificate signed off-chain by an org, submitted by its holder.
+		///
+		/// `signature` covers [`Self::claim_message`] of the payload, which binds it to this chain.
+		/// The holder pays the transaction, the storage deposit and the org's issuance fee.
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn claim_certificate(
+			origin: OriginFor<T>,
+			payload: CertificatePayload<T>,
+			signer: SigningKey,
+			signature: MultiSignature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who == payload.terms.holder, Error::<T>::NotCertificateHolder);
+			ensure!(
+				payload.valid_until > <pallet_utils::Pallet<T>>::unix_now(),
+				Error::<T>::ClaimExpired
+			);
+			ensure!(
+				<SigningKeys<T>>::contains_key(&payload.org, &signer),
+				Error::<T>::SigningKeyNotFound
+			);
+			ensure!(
+				signer.verify(&signature, &Self::claim_message(&payload)),
+				Error::<T>::InvalidSignature
+			);
+			ensure!(
+				!Self::claim_nonce_used(&payload.org, payload.nonce),
+				Error::<T>::ClaimNonceUsed
+			);
+
+			<UsedClaimNonces<T>>::insert(&payload.org, payload.nonce, true);
+			Self::pay_issuance_fee(&who, &payload.org)?;
+			let cid = Self::issue(&payload.org, &who, payload.terms, Vec::new(), None)?;
+
+			Self::deposit_event(Event::CertificateClaimed(who, cid));
+			Ok(())
+		}
+
+		/// Asks `org` for a certificate of class `class_id`, backed by `evidence`.
+		///
+		/// The org's issuance fee and the request deposit are reserved from the holder until the
+		/// request is answered.
+		#[pallet::weight(10_000)]
+		pub fn request_certificate(
+			origin: OriginFor<T>,
+			org: T::AccountId,
+			class_id: TypeID,
+			evidence: Content,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != org, Error::<T>::SelfRequest);
+			Self::ensure_org(&org)?;
+			let class = Self::class_by_id(class_id).ok_or(Error::<T>::ClassNotFound)?;
+			ensure!(class.org == org, Error::<T>::NotClassOwner);
+			<pallet_utils::Pallet<T>>::ensure_content_is_valid(&evidence)?;
+			ensure!(
+				<PendingRequestsByOrg<T>>::decode_len(&org).unwrap_or(0) <
+					T::MaxPendingRequests::get() as usize,
+				Error::<T>::TooManyPendingRequests
+			);
+
+			let request_id = Self::request_id();
+			let next_id = request_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			let fee = Self::issuance_fee(&org).unwrap_or_else(Zero::zero);
+			let deposit = T::RequestDeposit::get();
+			T::Currency::reserve(&who, fee.saturating_add(deposit))?;
+
+			<RequestById<T>>::insert(request_id, CertificateRequest {
+				request_id,
+				holder: who.clone(),
+				org: org.clone(),
+				class_id,
+				evidence,
+				created: WhoAndWhen::<T>::new(who.clone()),
+				expires: <pallet_utils::Pallet<T>>::unix_now()
+					.saturating_add(T::RequestTimeout::get()),
+				status: ApprovalStatus::Pending,
+				reason: None,
+				fee,
+				deposit,
+				cid: None,
+			});
+			<PendingRequestsByOrg<T>>::append(&org, request_id);
+			<RequestId<T>>::put(next_id);
+
+			Self::deposit_event(Event::CertificateRequested(who, org, request_id));
+			Ok(())
+		}
+
+		/// Approves a pending request, issuing the certificate to its holder.
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn approve_request(
+			origin: OriginFor<T>,
+			request_id: TypeID,
+			grade: Option<u32>,
+			metadata: String,
+			expires: Option<UnixEpoch>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut request = Self::pending_request(&who, request_id)?;
+			ensure!(
+				request.expires > <pallet_utils::Pallet<T>>::unix_now(),
+				Error::<T>::RequestExpired
+			);
+
+			let terms = CertificateTerms {
+				holder: request.holder.clone(),
+				class_id: request.class_id,
+				grade,
+				metadata,
+				expires,
+				content: Content::None,
+				content_hash: None,
+				claims_root: None,
+			};
+			let cid = Self::issue(&who, &who, terms, Vec::new(), None)?;
+			T::Currency::repatriate_reserved(
+				&request.holder,
+				&who,
+				request.fee,
+				BalanceStatus::Free,
+			)?;
+			T::Currency::unreserve(&request.holder, request.deposit);
+
+			request.status = ApprovalStatus::Allow;
+			request.cid = Some(cid);
+			Self::close_request(request);
+
+			Self::deposit_event(Event::RequestApproved(request_id, cid));
+			Ok(())
+		}
+
+		/// Rejects a pending request, refunding the holder's fee and deposit.
+		#[pallet::weight(10_000)]
+		pub fn reject_request(
+			origin: OriginFor<T>,
+			request_id: TypeID,
+			reason: String,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut request = Self::pending_request(&who, request_id)?;
+
+			T::Currency::unreserve(&request.holder, request.fee.saturating_add(request.deposit));
+			request.status = ApprovalStatus::Deny;
+			request.reason = Some(reason);
+			Self::close_request(request);
+
+			Self::deposit_event(Event::RequestRejected(request_id));
+			Ok(())
+		}
+
+		/// Drops a pending request, refunding the holder's fee and deposit.
+		///
+		/// The holder can cancel at any time; anyone can drop a request that timed out.
+		#[pallet::weight(10_000)]
+		pub fn cancel_request(origin: OriginFor<T>, request_id: TypeID) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let request = Self::request_by_id(request_id).ok_or(Error::<T>::RequestNotFound)?;
+			ensure!(request.status == ApprovalStatus::Pending, Error::<T>::RequestNotPending);
+			ensure!(
+				request.holder == who ||
+					request.expires <= <pallet_utils::Pallet<T>>::unix_now(),
+				Error::<T>::RequestNotExpired
+			);
+
+			T::Currency::unreserve(&request.holder, request.fee.saturating_add(request.deposit));
+			<PendingRequestsByOrg<T>>::mutate(&request.org, |ids| {
+				ids.retain(|id| *id != request_id)
 			});
-            <CertificateId<T>>::mutate(|n| {
-				*n += 1;
+			<RequestById<T>>::remove(request_id);
+
+			Self::deposit_event(Event::RequestCancelled(request_id));
+			Ok(())
+		}
+
+		/// Proposes a certificate issued jointly with `co_issuers`.
+		///
+		/// It stays pending until every co-issuer signed it with `cosign_certificate` before
+		/// `deadline`.
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn propose_joint_certificate(
+			origin: OriginFor<T>,
+			terms: CertificateTerms<T>,
+			co_issuers: Vec<T::AccountId>,
+			deadline: UnixEpoch,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!co_issuers.is_empty(), Error::<T>::InvalidCoIssuers);
+			for (i, org) in co_issuers.iter().enumerate() {
+				ensure!(
+					*org != who && !co_issuers[..i].contains(org),
+					Error::<T>::InvalidCoIssuers
+				);
+				Self::ensure_org(org)?;
+			}
+			ensure!(
+				deadline > <pallet_utils::Pallet<T>>::unix_now(),
+				Error::<T>::InvalidCosignDeadline
+			);
+
+			let co_issuers = co_issuers
+				.into_iter()
+				.map(|org| CoIssuer { org, signed: None, revocation: None })
+				.collect();
+			let cid = Self::issue(&who, &who, terms, co_issuers, Some(deadline))?;
+
+			Self::deposit_event(Event::JointCertificateProposed(who, cid));
+			Ok(())
+		}
+
+		/// Co-signs a pending joint certificate, activating it once all co-issuers signed.
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn cosign_certificate(origin: OriginFor<T>, cid: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let activated = <CertificateById<T>>::try_mutate(
+				cid,
+				|maybe_certificate| -> Result<bool, DispatchError> {
+					let certificate =
+						maybe_certificate.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
+					ensure!(
+						certificate.status == CertificateStatus::Pending,
+						Error::<T>::InvalidCertificateStatus
+					);
+					if let Some(deadline) = certificate.cosign_deadline {
+						ensure!(
+							<pallet_utils::Pallet<T>>::unix_now() < deadline,
+							Error::<T>::CosignDeadlinePassed
+						);
+					}
+					let co_issuer = certificate
+						.co_issuers
+						.iter_mut()
+						.find(|c| c.org == who)
+						.ok_or(Error::<T>::NotCertificateIssuer)?;
+					ensure!(co_issuer.signed.is_none(), Error::<T>::AlreadyCosigned);
+					co_issuer.signed = Some(WhoAndWhen::<T>::new(who.clone()));
+
+					if !certificate.co_issuers.iter().all(|c| c.signed.is_some()) {
+						return Ok(false)
+					}
+					certificate.status = CertificateStatus::Active;
+					certificate.token =
+						Some(Self::mint_token(&certificate.org, &certificate.holder, cid)?);
+					Ok(true)
+				},
+			)?;
+
+			Self::deposit_event(Event::CertificateCosigned(who, cid));
+			if activated {
+				Self::deposit_event(Event::CertificateActivated(cid));
+			}
+			Ok(())
+		}
+
+		/// Drops a pending joint certificate, refunding its storage deposit.
+		///
+		/// The proposer can cancel at any time; anyone can drop a certificate whose co-signing
+		/// deadline passed.
+		#[pallet::weight(10_000)]
+		pub fn cancel_joint_certificate(origin: OriginFor<T>, cid: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let certificate =
+				Self::certificate_by_id(cid).ok_or(Error::<T>::CertificateNotFound)?;
+			ensure!(
+				certificate.status == CertificateStatus::Pending,
+				Error::<T>::InvalidCertificateStatus
+			);
+			let now = <pallet_utils::Pallet<T>>::unix_now();
+			ensure!(
+				certificate.org == who ||
+					certificate.cosign_deadline.map_or(false, |deadline| deadline <= now),
+				Error::<T>::CosignDeadlineNotPassed
+			);
+
+			T::Currency::unreserve(&certificate.depositor, certificate.deposit);
+			if let Some(document) = certificate.content_hash {
+				<CertificateIdByDocument<T>>::remove(document, &certificate.org);
+			}
+			<CertificateIdByNumber<T>>::remove(certificate.number);
+			<CertificateById<T>>::remove(cid);
+
+			Self::deposit_event(Event::JointCertificateCancelled(cid));
+			Ok(())
+		}
+
+		/// Registers a key the calling org signs certificate payloads with.
+		#[pallet::weight(10_000)]
+		pub fn add_signing_key(origin: OriginFor<T>, key: SigningKey) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_org(&who)?;
+			ensure!(
+				!<SigningKeys<T>>::contains_key(&who, &key),
+				Error::<T>::SigningKeyAlreadyAdded
+			);
+
+			<SigningKeys<T>>::insert(&who, &key, WhoAndWhen::<T>::new(who.clone()));
+
+			Self::deposit_event(Event::SigningKeyAdded(who, key));
+			Ok(())
+		}
+
+		/// Removes a signing key of the calling org. Payloads it signed can no longer be claimed.
+		#[pallet::weight(10_000)]
+		pub fn remove_signing_key(origin: OriginFor<T>, key: SigningKey) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<SigningKeys<T>>::contains_key(&who, &key), Error::<T>::SigningKeyNotFound);
+
+			<SigningKeys<T>>::remove(&who, &key);
+
+			Self::deposit_event(Event::SigningKeyRemoved(who, key));
+			Ok(())
+		}
+
+		/// Sets the fee holders pay the calling org when requesting a certificate, or clears it.
+		#[pallet::weight(10_000)]
+		pub fn set_issuance_fee(origin: OriginFor<T>, fee: Option<BalanceOf<T>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_org(&who)?;
+
+			match fee {
+				Some(fee) => <IssuanceFeeByOrg<T>>::insert(&who, fee),
+				None => <IssuanceFeeByOrg<T>>::remove(&who),
+			}
+
+			Self::deposit_event(Event::IssuanceFeeSet(who, fee));
+			Ok(())
+		}
+
+		/// Creates a class of certificates issued by the calling org.
+		#[pallet::weight(10_000)]
+		pub fn create_class(
+			origin: OriginFor<T>,
+			kind: ClassKind,
+			name: String,
+			min_grade: u32,
+			max_grade: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_org(&who)?;
+
+			ensure!(min_grade <= max_grade, Error::<T>::InvalidGradeBounds);
+
+			let class_id = Self::class_id();
+			let next_id = class_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+
+			<ClassById<T>>::insert(class_id, CertificateClass {
+				class_id,
+				org: who.clone(),
+				kind,
+				name,
+				min_grade,
+				max_grade,
+				created: WhoAndWhen::<T>::new(who.clone()),
 			});
-			// Emit an event.
-			Self::deposit_event(Event::CertificateCreated(who));
-			// Return a successful DispatchResultWithPostInfo
+			<ClassId<T>>::put(next_id);
+
+			Self::deposit_event(Event::ClassCreated(who, class_id));
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
+		/// Revokes a certificate. The record is kept so verifiers can see why it was revoked, and
+		/// only the part of its deposit above the base deposit is refunded.
+		///
+		/// Any issuer of a joint certificate can revoke it; each issuer's revocation is recorded.
 		#[pallet::weight(10_000)]
-		pub fn revoke_certificate(origin: OriginFor<T>, _cid: TypeID) -> DispatchResult {
+		#[transactional]
+		pub fn revoke_certificate(
+			origin: OriginFor<T>,
+			_cid: T::Hash,
+			reason: String,
+		) -> DispatchResult {
 			let _who = ensure_signed(origin)?;
-			<CertificateById<T>>::remove(_cid);
+			let revoked = <CertificateById<T>>::try_mutate(_cid, |maybe_certificate| {
+				let certificate =
+					maybe_certificate.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
+				let revocation = if certificate.org == _who {
+					&mut certificate.revocation
+				} else {
+					&mut certificate
+						.co_issuers
+						.iter_mut()
+						.find(|c| c.org == _who)
+						.ok_or(Error::<T>::NotCertificateIssuer)?
+						.revocation
+				};
+				ensure!(revocation.is_none(), Error::<T>::CertificateAlreadyRevoked);
+				*revocation =
+					Some(Revocation { revoked: WhoAndWhen::<T>::new(_who.clone()), reason });
+
+				if certificate.status != CertificateStatus::Revoked {
+					if certificate.status == CertificateStatus::Suspended {
+						Self::set_status_bit(
+							&certificate.org,
+							StatusPurpose::Suspension,
+							certificate.status_index,
+							false,
+						);
+					}
+					certificate.status = CertificateStatus::Revoked;
+					Self::set_status_bit(
+						&certificate.org,
+						StatusPurpose::Revocation,
+						certificate.status_index,
+						true,
+					);
+					if let Some((collection, item)) = certificate.token.take() {
+						Self::burn_token(collection, item)?;
+					}
+					if let Some(document) = certificate.content_hash {
+						<CertificateIdByDocument<T>>::remove(document, &certificate.org);
+					}
+					let refund =
+						certificate.deposit.saturating_sub(T::CertificateDepositBase::get());
+					T::Currency::unreserve(&certificate.depositor, refund);
+					certificate.deposit = certificate.deposit.saturating_sub(refund);
+					return Ok(Some(certificate.clone()))
+				}
+				Ok::<_, DispatchError>(None)
+			})?;
+			if let Some(certificate) = revoked {
+				T::OnRevoke::on_certificate_revoked(&certificate);
+			}
 			Self::deposit_event(Event::CertificateRevoked(_who));
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
+
+		/// Temporarily suspends an active certificate.
+		#[pallet::weight(10_000)]
+		pub fn suspend_certificate(origin: OriginFor<T>, cid: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::set_certificate_status(
+				&who,
+				cid,
+				CertificateStatus::Active,
+				CertificateStatus::Suspended,
+			)?;
+			Self::deposit_event(Event::CertificateSuspended(cid));
+			Ok(())
+		}
+
+		/// Reinstates a suspended certificate.
+		#[pallet::weight(10_000)]
+		pub fn reinstate_certificate(origin: OriginFor<T>, cid: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::set_certificate_status(
+				&who,
+				cid,
+				CertificateStatus::Suspended,
+				CertificateStatus::Active,
+			)?;
+			Self::deposit_event(Event::CertificateReinstated(cid));
+			Ok(())
+		}
+
+		/// Anchors the Merkle `root` of a batch of `leaf_count` certificate hashes.
+		///
+		/// Leaves are computed off-chain; see [`crate::merkle`] for how the tree is built.
+		#[pallet::weight(10_000)]
+		pub fn anchor_batch(
+			origin: OriginFor<T>,
+			root: T::Hash,
+			leaf_count: u32,
+			metadata: String,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_org(&who)?;
+
+			ensure!(leaf_count > 0, Error::<T>::EmptyBatch);
+
+			let batch_id = Self::batch_id();
+			let next_id = batch_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+
+			<BatchById<T>>::insert(batch_id, CertificateBatch {
+				batch_id,
+				org: who.clone(),
+				root,
+				leaf_count,
+				created: WhoAndWhen::<T>::new(who.clone()),
+				metadata,
+			});
+			<BatchId<T>>::put(next_id);
+
+			Self::deposit_event(Event::BatchAnchored(who, batch_id));
+			Ok(())
+		}
+
+		/// Revokes a single certificate of an anchored batch.
+		#[pallet::weight(10_000)]
+		pub fn revoke_batch_leaf(
+			origin: OriginFor<T>,
+			batch_id: TypeID,
+			leaf: T::Hash,
+			reason: String,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let batch = Self::batch_by_id(batch_id).ok_or(Error::<T>::BatchNotFound)?;
+			ensure!(batch.org == who, Error::<T>::NotBatchIssuer);
+			ensure!(
+				!<RevokedBatchLeaves<T>>::contains_key(batch_id, leaf),
+				Error::<T>::LeafAlreadyRevoked
+			);
+
+			<RevokedBatchLeaves<T>>::insert(batch_id, leaf, Revocation {
+				revoked: WhoAndWhen::<T>::new(who),
+				reason,
+			});
+
+			Self::deposit_event(Event::BatchLeafRevoked(batch_id, leaf));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn ensure_org(who: &T::AccountId) -> DispatchResult {
+			ensure!(<pallet_sys_man::Org<T>>::contains_key(who), Error::<T>::NotAnOrganization);
+			Ok(())
+		}
+
+		/// Issues a certificate on behalf of `org`, reserving its storage deposit from `depositor`.
+		///
+		/// A joint certificate stays pending, without a token, until all `co_issuers` signed it.
+		fn issue(
+			org: &T::AccountId,
+			depositor: &T::AccountId,
+			terms: CertificateTerms<T>,
+			co_issuers: Vec<CoIssuer<T>>,
+			cosign_deadline: Option<UnixEpoch>,
+		) -> Result<T::Hash, DispatchError> {
+			Self::ensure_org(org)?;
+			<pallet_utils::Pallet<T>>::ensure_content_is_valid(&terms.content)?;
+			if let Some(expires) = terms.expires {
+				ensure!(expires > <pallet_utils::Pallet<T>>::unix_now(), Error::<T>::InvalidExpiry);
+			}
+			ensure!(
+				terms.claims_root.is_none() || terms.metadata.is_empty(),
+				Error::<T>::MetadataWithClaimsRoot
+			);
+			if let Some(document) = terms.content_hash {
+				ensure!(
+					!<CertificateIdByDocument<T>>::contains_key(document, org),
+					Error::<T>::DocumentAlreadyAnchored
+				);
+			}
+			let score = Self::score(org, terms.class_id, terms.grade)?;
+			let deposit = Self::deposit_for(&terms.metadata);
+			T::Currency::reserve(depositor, deposit)?;
+			let number = Self::certificate_id();
+			let next_number = number.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			let cid = <pallet_utils::Pallet<T>>::derive_id(b"certificate", org, number);
+			let status_index = Self::next_status_index(org);
+			let next_status_index =
+				status_index.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			let (status, token) = if co_issuers.is_empty() {
+				(CertificateStatus::Active, Some(Self::mint_token(org, &terms.holder, cid)?))
+			} else {
+				(CertificateStatus::Pending, None)
+			};
+			// Update storage.
+			<CertificateById<T>>::insert(cid, Certificate {
+				cid,
+				number,
+				org: org.clone(),
+				holder: terms.holder,
+				class_id: terms.class_id,
+				grade: terms.grade,
+				created: WhoAndWhen::<T>::new(org.clone()),
+				metadata: terms.metadata,
+				score,
+				content: terms.content,
+				content_hash: terms.content_hash,
+				claims_root: terms.claims_root,
+				expires: terms.expires,
+				status,
+				revocation: None,
+				token,
+				co_issuers,
+				cosign_deadline,
+				status_index,
+				depositor: depositor.clone(),
+				deposit,
+			});
+			if let Some(document) = terms.content_hash {
+				<CertificateIdByDocument<T>>::insert(document, org, cid);
+			}
+			<CertificateIdByNumber<T>>::insert(number, cid);
+			<CertificateId<T>>::put(next_number);
+			<NextStatusIndex<T>>::insert(org, next_status_index);
+			// Emit an event.
+			Self::deposit_event(Event::CertificateCreated(org.clone()));
+			Ok(cid)
+		}
+
+		/// Pending request `request_id`, provided it was sent to `org`.
+		fn pending_request(
+			org: &T::AccountId,
+			request_id: TypeID,
+		) -> Result<CertificateRequest<T>, DispatchError> {
+			let request = Self::request_by_id(request_id).ok_or(Error::<T>::RequestNotFound)?;
+			ensure!(request.org == *org, Error::<T>::NotRequestRecipient);
+			ensure!(request.status == ApprovalStatus::Pending, Error::<T>::RequestNotPending);
+			Ok(request)
+		}
+
+		/// Stores an answered request and removes it from its org's queue.
+		fn close_request(request: CertificateRequest<T>) {
+			let request_id = request.request_id;
+			<PendingRequestsByOrg<T>>::mutate(&request.org, |ids| {
+				ids.retain(|id| *id != request_id)
+			});
+			<RequestById<T>>::insert(request_id, request);
+		}
+
+		/// Storage deposit of a certificate carrying `metadata`.
+		pub fn deposit_for(metadata: &[u8]) -> BalanceOf<T> {
+			let len = BalanceOf::<T>::from(metadata.len() as u32);
+			T::CertificateDepositBase::get()
+				.saturating_add(T::CertificateDepositPerByte::get().saturating_mul(len))
+		}
+
+		/// Message an org signs for `payload` to be claimed: the SCALE encoding of
+		/// `(b"scv/claim", genesis_hash, payload)`, that is the 9 bytes `scv/claim`, the hash of
+		/// the genesis block of this chain and the encoded payload. The tag keeps the signature
+		/// from being valid for anything but a claim, the genesis hash from being claimed on
+		/// another network.
+		pub fn claim_message(payload: &CertificatePayload<T>) -> Vec<u8> {
+			let genesis = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+			(b"scv/claim", genesis, payload).encode()
+		}
+
+		/// Charges `holder` the issuance fee of `org`, if it has set one.
+		fn pay_issuance_fee(holder: &T::AccountId, org: &T::AccountId) -> DispatchResult {
+			if let Some(fee) = Self::issuance_fee(org) {
+				T::Currency::transfer(holder, org, fee, ExistenceRequirement::KeepAlive)?;
+			}
+			Ok(())
+		}
+
+		/// Mints the soulbound token of certificate `cid` to `holder`, in the collection of `org`.
+		fn mint_token(
+			org: &T::AccountId,
+			holder: &T::AccountId,
+			cid: T::Hash,
+		) -> Result<(TypeID, TypeID), DispatchError> {
+			let collection = match Self::collection_by_org(org) {
+				Some(collection) => collection,
+				None => {
+					let collection = Self::next_collection_id();
+					let next_id = collection.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+					T::Nfts::create_class(&collection, org, org)?;
+					<CollectionByOrg<T>>::insert(org, collection);
+					<NextCollectionId<T>>::put(next_id);
+					collection
+				},
+			};
+
+			let item = Self::next_token_id(collection);
+			let next_id = item.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			T::Nfts::mint_into(&collection, &item, holder)?;
+			<NextTokenId<T>>::insert(collection, next_id);
+			<CertificateByToken<T>>::insert(collection, item, cid);
+
+			Ok((collection, item))
+		}
+
+		fn burn_token(collection: TypeID, item: TypeID) -> DispatchResult {
+			T::Nfts::burn_from(&collection, &item)?;
+			<CertificateByToken<T>>::remove(collection, item);
+			Ok(())
+		}
+
+		/// Scores a certificate of class `class_id` issued by `org` with the given `grade`.
+		fn score(
+			org: &T::AccountId,
+			class_id: TypeID,
+			grade: Option<u32>,
+		) -> Result<u32, DispatchError> {
+			let class = Self::class_by_id(class_id).ok_or(Error::<T>::ClassNotFound)?;
+			ensure!(class.org == *org, Error::<T>::NotClassOwner);
+			if let Some(grade) = grade {
+				ensure!(
+					class.min_grade <= grade && grade <= class.max_grade,
+					Error::<T>::GradeOutOfBounds
+				);
+			}
+
+			let profile = <pallet_sys_man::Pallet<T>>::org_profile(org);
+			Ok(T::ScoringPolicy::score(&ScoreContext {
+				category: profile.category,
+				reputation: profile.reputation,
+				class: class.kind,
+				grade,
+				min_grade: class.min_grade,
+				max_grade: class.max_grade,
+			}))
+		}
+
+		fn set_certificate_status(
+			who: &T::AccountId,
+			cid: T::Hash,
+			from: CertificateStatus,
+			to: CertificateStatus,
+		) -> DispatchResult {
+			<CertificateById<T>>::try_mutate(cid, |maybe_certificate| -> DispatchResult {
+				let certificate =
+					maybe_certificate.as_mut().ok_or(Error::<T>::CertificateNotFound)?;
+				ensure!(certificate.org == *who, Error::<T>::NotCertificateIssuer);
+				ensure!(certificate.status == from, Error::<T>::InvalidCertificateStatus);
+				let suspended = to == CertificateStatus::Suspended;
+				certificate.status = to;
+				Self::set_status_bit(
+					&certificate.org,
+					StatusPurpose::Suspension,
+					certificate.status_index,
+					suspended,
+				);
+				Ok(())
+			})
+		}
+
+		/// Sets or clears the bit at `index` of the `purpose` status list of `org`.
+		fn set_status_bit(org: &T::AccountId, purpose: StatusPurpose, index: u32, value: bool) {
+			<StatusLists<T>>::mutate(org, purpose, |list| {
+				let byte = (index / 8) as usize;
+				if list.len() <= byte {
+					list.resize(byte + 1, 0);
+				}
+				let mask = 0x80 >> (index % 8);
+				if value {
+					list[byte] |= mask;
+				} else {
+					list[byte] &= !mask;
+				}
+			});
+		}
+
+		/// Ids of the unrevoked certificates anchoring the document hash `document`, one per org.
+		pub fn certificates_by_document(document: DocumentHash) -> Vec<T::Hash> {
+			<CertificateIdByDocument<T>>::iter_prefix_values(document).collect()
+		}
+
+		/// Tells whether certificate `cid` is valid for `holder` at the current block.
+		pub fn verify(cid: T::Hash, holder: T::AccountId) -> Verdict {
+			let certificate = match Self::certificate_by_id(cid) {
+				Some(certificate) if certificate.holder == holder => certificate,
+				_ => return Verdict::Unknown,
+			};
+
+			if certificate.status == CertificateStatus::Revoked {
+				let reason =
+					certificate.revocations().next().map(|r| r.reason.clone()).unwrap_or_default();
+				return Verdict::Revoked(reason)
+			}
+			if certificate.issuers().any(<pallet_sys_man::OrgRevoked<T>>::contains_key) {
+				return Verdict::IssuerRevoked
+			}
+			match certificate.status {
+				CertificateStatus::Pending => return Verdict::Pending,
+				CertificateStatus::Suspended => return Verdict::Suspended,
+				_ => {},
+			}
+			match certificate.expires {
+				Some(expires) if expires <= <pallet_utils::Pallet<T>>::unix_now() =>
+					Verdict::Expired,
+				_ => Verdict::Valid,
+			}
+		}
+
+		/// Checks a field revealed by the holder against the claims root of certificate `cid`.
+		///
+		/// This only proves the field was committed to at issuance; use [`Self::verify`] to check
+		/// that the certificate itself is still valid.
+		pub fn verify_disclosure(
+			cid: T::Hash,
+			key: String,
+			value: String,
+			salt: String,
+			proof: Vec<T::Hash>,
+		) -> bool {
+			let root = match Self::certificate_by_id(cid).and_then(|c| c.claims_root) {
+				Some(root) => root,
+				None => return false,
+			};
+			let leaf = crate::merkle::claim_leaf::<T::Hashing>(&salt, &key, &value);
+			crate::merkle::verify::<T::Hashing>(&root, leaf, &proof)
+		}
+
+		/// Checks the Merkle `proof` that `leaf` belongs to the batch `batch_id`.
+		pub fn verify_batch_leaf(
+			batch_id: TypeID,
+			leaf: T::Hash,
+			proof: Vec<T::Hash>,
+		) -> BatchLeafStatus {
+			let batch = match Self::batch_by_id(batch_id) {
+				Some(batch) => batch,
+				None => return BatchLeafStatus::UnknownBatch,
+			};
+
+			if proof.len() > crate::merkle::max_proof_len(batch.leaf_count) ||
+				!crate::merkle::verify::<T::Hashing>(&batch.root, leaf, &proof)
+			{
+				BatchLeafStatus::NotIncluded
+			} else if <RevokedBatchLeaves<T>>::contains_key(batch_id, leaf) {
+				BatchLeafStatus::Revoked
+			} else {
+				BatchLeafStatus::Valid
+			}
+		}
 	}
 }
@@ -0,0 +1,90 @@
+//! Binary Merkle trees used to anchor batches of certificates and to commit to the individual
+//! fields of a certificate.
+//!
+//! Sibling nodes are ordered before being hashed together, so an inclusion proof is just the list
+//! of sibling hashes from the leaf up to the root. An unpaired node is promoted to the next level
+//! unchanged.
+//!
+//! Leaves and inner nodes are hashed in separate domains, `0x00 ++ leaf` and
+//! `0x01 ++ left ++ right`, so an inner node can never be passed off as a leaf.
+
+use frame_support::inherent::Vec;
+use sp_runtime::traits::Hash;
+
+/// Commitment to a single certificate field: the hash of the SCALE-encoded `(salt, key, value)`.
+///
+/// The salt keeps low-entropy values such as grades from being guessed from the leaf.
+pub fn claim_leaf<H: Hash>(salt: &[u8], key: &[u8], value: &[u8]) -> H::Output {
+	H::hash_of(&(salt, key, value))
+}
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hashes a leaf into the node it occupies in the tree.
+pub fn hash_leaf<H: Hash>(leaf: &H::Output) -> H::Output {
+	let mut buf = Vec::with_capacity(1 + leaf.as_ref().len());
+	buf.push(LEAF_PREFIX);
+	buf.extend_from_slice(leaf.as_ref());
+	H::hash(&buf)
+}
+
+/// Hashes two sibling nodes into their parent.
+pub fn hash_pair<H: Hash>(a: &H::Output, b: &H::Output) -> H::Output {
+	let (left, right) = if a.as_ref() <= b.as_ref() { (a, b) } else { (b, a) };
+	let mut buf = Vec::with_capacity(1 + left.as_ref().len() + right.as_ref().len());
+	buf.push(NODE_PREFIX);
+	buf.extend_from_slice(left.as_ref());
+	buf.extend_from_slice(right.as_ref());
+	H::hash(&buf)
+}
+
+fn next_level<H: Hash>(level: &[H::Output]) -> Vec<H::Output> {
+	level
+		.chunks(2)
+		.map(|pair| match pair {
+			[a, b] => hash_pair::<H>(a, b),
+			_ => pair[0],
+		})
+		.collect()
+}
+
+/// Computes the root of the tree over `leaves`, or `None` if there are no leaves.
+pub fn root<H: Hash>(leaves: &[H::Output]) -> Option<H::Output> {
+	let mut level: Vec<H::Output> = leaves.iter().map(hash_leaf::<H>).collect();
+	while level.len() > 1 {
+		level = next_level::<H>(&level);
+	}
+	level.pop()
+}
+
+/// Builds the inclusion proof for the leaf at `index`.
+pub fn proof<H: Hash>(leaves: &[H::Output], mut index: usize) -> Option<Vec<H::Output>> {
+	if index >= leaves.len() {
+		return None
+	}
+	let mut proof = Vec::new();
+	let mut level: Vec<H::Output> = leaves.iter().map(hash_leaf::<H>).collect();
+	while level.len() > 1 {
+		let sibling = index ^ 1;
+		if sibling < level.len() {
+			proof.push(level[sibling]);
+		}
+		level = next_level::<H>(&level);
+		index /= 2;
+	}
+	Some(proof)
+}
+
+/// Checks that `leaf` is included in the tree with the given `root`.
+pub fn verify<H: Hash>(root: &H::Output, leaf: H::Output, proof: &[H::Output]) -> bool {
+	proof
+		.iter()
+		.fold(hash_leaf::<H>(&leaf), |node, sibling| hash_pair::<H>(&node, sibling)) ==
+		*root
+}
+
+/// Length of the longest inclusion proof in a tree of `leaf_count` leaves, `ceil(log2(n))`.
+pub fn max_proof_len(leaf_count: u32) -> usize {
+	(u32::BITS - leaf_count.saturating_sub(1).leading_zeros()) as usize
+}
@@ -0,0 +1,146 @@
+//! Storage migrations of the certificate pallet.
+
+pub mod v1 {
+	//! Moves certificates from sequential `u32` ids to hash ids derived with
+	//! [`pallet_utils::Pallet::derive_id`]. The old id is kept as the certificate number, so
+	//! certificates can still be looked up by it through `CertificateIdByNumber`.
+	//!
+	//! Certificates had no holder, class or deposit before v1. They are migrated as active
+	//! certificates held by their org, with nothing reserved, in a "Legacy certificates" class
+	//! created for each org. Each org gets status list indexes in the order of the legacy
+	//! numbers of its certificates.
+
+	use crate::*;
+	use codec::{Decode, Input};
+	use core::marker::PhantomData;
+	use frame_support::{
+		inherent::Vec,
+		log,
+		sp_std::collections::btree_map::BTreeMap,
+		storage::migration::{storage_key_iter, take_storage_item},
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+		weights::Weight,
+		Twox64Concat,
+	};
+	use pallet_utils::{Content, String, TypeID, WhoAndWhen};
+	use sp_runtime::traits::Zero;
+
+	/// SCALE encoding of a certificate stored before v1, read as is so entries that do not
+	/// decode can be told apart and left in place.
+	struct RawCertificate(Vec<u8>);
+
+	impl Decode for RawCertificate {
+		fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+			let len = input.remaining_len()?.ok_or("unknown certificate length")?;
+			let mut bytes = Vec::new();
+			bytes.resize(len, 0);
+			input.read(&mut bytes)?;
+			Ok(Self(bytes))
+		}
+	}
+
+	/// A certificate stored before v1.
+	#[derive(Decode)]
+	struct LegacyCertificate<T: Config> {
+		cid: TypeID,
+		org: T::AccountId,
+		scrore: u32,
+		metadata: String,
+	}
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return 0
+			}
+
+			let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			let legacy: Vec<(TypeID, RawCertificate)> =
+				storage_key_iter::<TypeID, RawCertificate, Twox64Concat>(pallet, b"CertificateById")
+					.collect();
+			let reads = legacy.len() as Weight;
+			let mut writes = 0;
+
+			let mut decoded: Vec<(TypeID, LegacyCertificate<T>)> = legacy
+				.into_iter()
+				.filter_map(|(key, RawCertificate(raw))| {
+					match LegacyCertificate::<T>::decode(&mut &raw[..]) {
+						Ok(old) => Some((key, old)),
+						Err(_) => {
+							log::warn!(
+								target: "runtime::certificate",
+								"certificate {} does not decode, left under its legacy id",
+								key,
+							);
+							None
+						},
+					}
+				})
+				.collect();
+			// Storage is iterated in hash order, status list indexes follow the legacy numbers.
+			decoded.sort_by_key(|(_, old)| old.cid);
+
+			let mut legacy_classes = BTreeMap::new();
+			for (key, old) in decoded {
+				let number = old.cid;
+				let cid = <pallet_utils::Pallet<T>>::derive_id(b"certificate", &old.org, number);
+				let class_id = match legacy_classes.get(&old.org) {
+					Some(class_id) => *class_id,
+					None => {
+						let class_id = Pallet::<T>::class_id();
+						<ClassById<T>>::insert(class_id, CertificateClass {
+							class_id,
+							org: old.org.clone(),
+							kind: ClassKind::Diploma,
+							name: b"Legacy certificates".to_vec(),
+							min_grade: 0,
+							max_grade: 0,
+							created: WhoAndWhen::<T>::new(old.org.clone()),
+						});
+						<ClassId<T>>::put(class_id.saturating_add(1));
+						legacy_classes.insert(old.org.clone(), class_id);
+						writes += 2;
+						class_id
+					},
+				};
+				let status_index = Pallet::<T>::next_status_index(&old.org);
+				let _ = take_storage_item::<TypeID, RawCertificate, Twox64Concat>(
+					pallet,
+					b"CertificateById",
+					key,
+				);
+				<CertificateById<T>>::insert(cid, Certificate {
+					cid,
+					number,
+					org: old.org.clone(),
+					holder: old.org.clone(),
+					class_id,
+					grade: None,
+					created: WhoAndWhen::<T>::new(old.org.clone()),
+					score: old.scrore,
+					metadata: old.metadata,
+					content: Content::None,
+					content_hash: None,
+					claims_root: None,
+					expires: None,
+					status: CertificateStatus::Active,
+					revocation: None,
+					token: None,
+					co_issuers: Vec::new(),
+					cosign_deadline: None,
+					status_index,
+					depositor: old.org.clone(),
+					deposit: Zero::zero(),
+				});
+				<CertificateIdByNumber<T>>::insert(number, cid);
+				<NextStatusIndex<T>>::insert(&old.org, status_index.saturating_add(1));
+				writes += 4;
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes + 1)
+		}
+	}
+}
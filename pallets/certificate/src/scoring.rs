@@ -0,0 +1,65 @@
+//! Scoring of certificates.
+//!
+//! The score of a certificate feeds CV ranking, so it is decided by a policy plugged into the
+//! pallet's `Config` rather than by the issuer.
+
+use crate::ClassKind;
+use pallet_utils::OrgCategory;
+
+/// Everything a [`ScoringPolicy`] may take into account.
+#[derive(Clone, Eq, PartialEq, sp_runtime::RuntimeDebug)]
+pub struct ScoreContext {
+	/// Category of the issuing organization.
+	pub category: OrgCategory,
+	/// Reputation of the issuing organization, out of `pallet_sys_man::MAX_REPUTATION`.
+	pub reputation: u8,
+	/// Kind of the certificate class.
+	pub class: ClassKind,
+	/// Grade awarded by the issuer, if any, within `min_grade..=max_grade`.
+	pub grade: Option<u32>,
+	pub min_grade: u32,
+	pub max_grade: u32,
+}
+
+/// Decides the score of a newly issued certificate.
+pub trait ScoringPolicy {
+	fn score(context: &ScoreContext) -> u32;
+}
+
+/// Scores certificates out of 100.
+///
+/// The class sets the base points, which are then weighted by the issuer's category, the
+/// issuer's reputation (50% to 100%) and the grade (50% to 100%, 75% when ungraded).
+pub struct DefaultScoringPolicy;
+
+impl ScoringPolicy for DefaultScoringPolicy {
+	fn score(context: &ScoreContext) -> u32 {
+		let base: u64 = match context.class {
+			ClassKind::Degree => 100,
+			ClassKind::Diploma => 80,
+			ClassKind::License => 70,
+			ClassKind::Award => 60,
+			ClassKind::Course => 45,
+			ClassKind::Training => 40,
+		};
+		let category: u64 = match context.category {
+			OrgCategory::University | OrgCategory::Government => 100,
+			OrgCategory::Company => 90,
+			OrgCategory::School => 80,
+			OrgCategory::TrainingProvider => 70,
+			OrgCategory::Other => 50,
+		};
+		let max_reputation = u64::from(pallet_sys_man::MAX_REPUTATION);
+		let reputation =
+			50 + 50 * u64::from(context.reputation).min(max_reputation) / max_reputation;
+		let grade = match context.grade {
+			Some(grade) if context.max_grade > context.min_grade =>
+				50 + 50 * u64::from(grade.saturating_sub(context.min_grade)) /
+					u64::from(context.max_grade - context.min_grade),
+			Some(_) => 100,
+			None => 75,
+		};
+
+		(base * category * reputation * grade / 1_000_000) as u32
+	}
+}
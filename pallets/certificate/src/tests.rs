@@ -1,20 +1,1237 @@
+//! pallet-certificate functionalities test
+use super::*;
 use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, traits::tokens::nonfungibles::Inspect};
+use pallet_utils::{ApprovalStatus, Content, OrgCategory, Role, Status, TypeID};
+use sp_core::{ed25519, sr25519, Pair, H256};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	MultiSignature,
+};
+
+fn str2vec(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
+
+fn register_org(id: u64) {
+	pallet_sys_man::Org::<Test>::insert(
+		id,
+		pallet_sys_man::SysManAccount::<Test> {
+			role: Role::Organization,
+			status: Status::Active,
+			level: None,
+			parent: None,
+			children: None,
+			metadata: vec![],
+		},
+	);
+}
+
+/// Id of the `number`-th certificate.
+fn cid(number: TypeID) -> H256 {
+	CertificateModule::certificate_id_by_number(number).unwrap()
+}
+
+/// Registers org `id` with a graded degree class, returning the class id.
+fn register_org_with_class(id: u64) -> TypeID {
+	register_org(id);
+	let class_id = CertificateModule::class_id();
+	assert_ok!(CertificateModule::create_class(
+		Origin::signed(id),
+		ClassKind::Degree,
+		str2vec("BSc in Computer Science"),
+		0,
+		100
+	));
+	class_id
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn create_certificate_should_work() {
 	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		let metadata = str2vec(r#"{"degree": "Bachelor of Science"}"#);
+
 		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(1),
+			2u64,
+			0,
+			None,
+			metadata.clone(),
+			None,
+			Content::None,
+			None,
+			None
+		));
+
+		let certificate = CertificateById::<Test>::get(cid(0)).unwrap();
+
+		assert_eq!(certificate.cid, cid(0));
+		assert_eq!(certificate.number, 0);
+		assert_eq!(certificate.org, 1);
+		assert_eq!(certificate.holder, 2);
+		assert_eq!(certificate.created.account, 1);
+		assert_eq!(certificate.created.block, 1);
+		assert_eq!(certificate.metadata, metadata);
+		assert_eq!(certificate.status, CertificateStatus::Active);
+		assert_eq!(CertificateModule::certificate_id(), 1);
+	});
+}
+
+#[test]
+fn certificate_token_should_be_minted_to_holder() {
+	new_test_ext().execute_with(|| {
+		let class_id = register_org_with_class(1);
+		let _ = register_org_with_class(3);
+
+		for (org, holder) in [(1u64, 2u64), (3, 2), (1, 4)] {
+			assert_ok!(CertificateModule::create_certificate(
+				Origin::signed(org),
+				holder,
+				if org == 1 { class_id } else { class_id + 1 },
+				None,
+				vec![],
+				None,
+				Content::None,
+				None,
+				None
+			));
+		}
+
+		// one collection per org, tokens numbered within each collection
+		assert_eq!(CertificateModule::collection_by_org(1), Some(0));
+		assert_eq!(CertificateModule::collection_by_org(3), Some(1));
+		assert_eq!(CertificateById::<Test>::get(cid(0)).unwrap().token, Some((0, 0)));
+		assert_eq!(CertificateById::<Test>::get(cid(1)).unwrap().token, Some((1, 0)));
+		assert_eq!(CertificateById::<Test>::get(cid(2)).unwrap().token, Some((0, 1)));
+
+		assert_eq!(<Uniques as Inspect<u64>>::owner(&0, &1), Some(4));
+		assert_eq!(CertificateModule::certificate_by_token(0, 1), Some(cid(2)));
+	});
+}
+
+#[test]
+fn certificate_score_should_follow_policy() {
+	new_test_ext().execute_with(|| {
+		let class_id = register_org_with_class(1);
+		let issue = |grade| {
+			assert_ok!(CertificateModule::create_certificate(
+				Origin::signed(1),
+				2u64,
+				class_id,
+				grade,
+				vec![],
+				None,
+				Content::None,
+				None,
+				None
+			));
+			let number = CertificateModule::certificate_id() - 1;
+			CertificateById::<Test>::get(cid(number)).unwrap().score
+		};
+
+		// unknown category and no reputation: 100 * 50% * 50% * 75%
+		assert_eq!(issue(None), 18);
+
+		pallet_sys_man::OrgProfiles::<Test>::insert(
+			1,
+			pallet_sys_man::OrgProfile { category: OrgCategory::University, reputation: 100 },
+		);
+		assert_eq!(issue(None), 75);
+		assert_eq!(issue(Some(100)), 100);
+		assert_eq!(issue(Some(0)), 50);
+
+		assert_noop!(
+			CertificateModule::create_certificate(
+				Origin::signed(1),
+				2u64,
+				class_id,
+				Some(101),
+				vec![],
+				None,
+				Content::None,
+				None,
+				None
+			),
+			Error::<Test>::GradeOutOfBounds
+		);
+	});
+}
+
+#[test]
+fn create_certificate_requires_own_class() {
+	new_test_ext().execute_with(|| {
+		let class_id = register_org_with_class(1);
+		register_org(3);
+
+		assert_noop!(
+			CertificateModule::create_certificate(
+				Origin::signed(3),
+				2u64,
+				class_id,
+				None,
+				vec![],
+				None,
+				Content::None,
+				None,
+				None
+			),
+			Error::<Test>::NotClassOwner
+		);
+		assert_noop!(
+			CertificateModule::create_class(Origin::signed(3), ClassKind::Course, vec![], 10, 5),
+			Error::<Test>::InvalidGradeBounds
+		);
+	});
+}
+
+#[test]
+fn create_certificate_requires_org() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CertificateModule::create_certificate(
+				Origin::signed(1),
+				2u64,
+				0,
+				None,
+				vec![],
+				None,
+				Content::None,
+				None,
+				None
+			),
+			Error::<Test>::NotAnOrganization
+		);
+	});
+}
+
+#[test]
+fn revoke_certificate_should_work() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(1),
+			2u64,
+			0,
+			None,
+			vec![],
+			None,
+			Content::None,
+			None,
+			None
+		));
+
+		// only the issuer can revoke a certificate
+		assert_noop!(
+			CertificateModule::revoke_certificate(Origin::signed(2), cid(0), vec![]),
+			Error::<Test>::NotCertificateIssuer
+		);
+
+		assert_ok!(CertificateModule::revoke_certificate(
+			Origin::signed(1),
+			cid(0),
+			str2vec("plagiarism")
+		));
+
+		let certificate = CertificateById::<Test>::get(cid(0)).unwrap();
+		assert_eq!(certificate.status, CertificateStatus::Revoked);
+		// revocation burns the soulbound token
+		assert_eq!(certificate.token, None);
+		assert_eq!(<Uniques as Inspect<u64>>::owner(&0, &0), None);
+		assert_eq!(CertificateModule::certificate_by_token(0, 0), None);
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Revoked(str2vec("plagiarism")));
+
+		assert_noop!(
+			CertificateModule::revoke_certificate(Origin::signed(1), cid(0), vec![]),
+			Error::<Test>::CertificateAlreadyRevoked
+		);
+	});
+}
+
+#[test]
+fn certificate_deposit_should_be_reserved_and_refunded() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(1),
+			2u64,
+			0,
+			None,
+			str2vec("transcript"),
+			None,
+			Content::None,
+			None,
+			None
+		));
+
+		// base deposit plus one unit per metadata byte
+		assert_eq!(CertificateModule::certificate_by_id(cid(0)).unwrap().deposit, 20);
+		assert_eq!(Balances::reserved_balance(1), 20);
+		assert_eq!(Balances::free_balance(1), 980);
+
+		// the base deposit stays reserved for the revoked record
+		assert_ok!(CertificateModule::revoke_certificate(Origin::signed(1), cid(0), vec![]));
+		assert_eq!(CertificateModule::certificate_by_id(cid(0)).unwrap().deposit, 10);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::free_balance(1), 990);
+	});
+}
+
+#[test]
+fn create_certificate_requires_deposit() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		assert_noop!(
+			CertificateModule::create_certificate(
+				Origin::signed(1),
+				2u64,
+				0,
+				None,
+				vec![0; 1_000],
+				None,
+				Content::None,
+				None,
+				None
+			),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn issuance_fee_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CertificateModule::set_issuance_fee(Origin::signed(1), Some(50)),
+			Error::<Test>::NotAnOrganization
+		);
+
+		register_org_with_class(1);
+		assert_ok!(CertificateModule::set_issuance_fee(Origin::signed(1), Some(50)));
+		assert_eq!(CertificateModule::issuance_fee(1), Some(50));
+
+		// holders requesting a certificate are charged the fee on top of the request deposit
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(2), 1, 0, Content::None));
+		assert_eq!(Balances::reserved_balance(2), 55);
+
+		assert_ok!(CertificateModule::set_issuance_fee(Origin::signed(1), None));
+		assert_eq!(CertificateModule::issuance_fee(1), None);
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(2), 1, 0, Content::None));
+		assert_eq!(Balances::reserved_balance(2), 60);
+	});
+}
+
+#[test]
+fn verify_certificate_should_work() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		Timestamp::set_timestamp(1_000_000);
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(1),
+			2u64,
+			0,
+			None,
+			vec![],
+			Some(2_000),
+			Content::None,
+			None,
+			None
+		));
+
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Valid);
+		// wrong holder or unknown certificate
+		assert_eq!(CertificateModule::verify(cid(0), 3), Verdict::Unknown);
+		assert_eq!(CertificateModule::verify(H256::zero(), 2), Verdict::Unknown);
+
+		assert_ok!(CertificateModule::suspend_certificate(Origin::signed(1), cid(0)));
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Suspended);
+		assert_ok!(CertificateModule::reinstate_certificate(Origin::signed(1), cid(0)));
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Valid);
+
+		Timestamp::set_timestamp(2_000_000);
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Expired);
+
+		pallet_sys_man::OrgRevoked::<Test>::insert(1, pallet_sys_man::Org::<Test>::get(1).unwrap());
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::IssuerRevoked);
+	});
+}
+
+#[test]
+fn selective_disclosure_should_work() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		let claims =
+			[("degree", "BSc", "s1"), ("gpa", "2.9", "s2"), ("birth_date", "1999-01-01", "s3")];
+		let leaves: Vec<H256> = claims
+			.iter()
+			.map(|(key, value, salt)| {
+				merkle::claim_leaf::<BlakeTwo256>(salt.as_bytes(), key.as_bytes(), value.as_bytes())
+			})
+			.collect();
+		let root = merkle::root::<BlakeTwo256>(&leaves).unwrap();
+
+		// committed fields must not also be stored in plaintext
+		assert_noop!(
+			CertificateModule::create_certificate(
+				Origin::signed(1),
+				2u64,
+				0,
+				None,
+				str2vec("degree: BSc, gpa: 2.9"),
+				None,
+				Content::None,
+				None,
+				Some(root)
+			),
+			Error::<Test>::MetadataWithClaimsRoot
+		);
+
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(1),
+			2u64,
+			0,
+			None,
+			vec![],
+			None,
+			Content::None,
+			None,
+			Some(root)
+		));
+
+		// the holder reveals the degree only
+		let proof = merkle::proof::<BlakeTwo256>(&leaves, 0).unwrap();
+		assert!(CertificateModule::verify_disclosure(
+			cid(0),
+			str2vec("degree"),
+			str2vec("BSc"),
+			str2vec("s1"),
+			proof.clone()
+		));
+
+		// a forged value, a wrong salt or an unknown certificate do not verify
+		assert!(!CertificateModule::verify_disclosure(
+			cid(0),
+			str2vec("degree"),
+			str2vec("PhD"),
+			str2vec("s1"),
+			proof.clone()
+		));
+		assert!(!CertificateModule::verify_disclosure(
+			cid(0),
+			str2vec("degree"),
+			str2vec("BSc"),
+			str2vec("s2"),
+			proof.clone()
+		));
+		assert!(!CertificateModule::verify_disclosure(
+			H256::zero(),
+			str2vec("degree"),
+			str2vec("BSc"),
+			str2vec("s1"),
+			proof
+		));
+	});
+}
+
+#[test]
+fn create_certificate_with_document_should_work() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		let diploma = Content::IPFS(str2vec("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"));
+		let diploma_hash = DocumentHash::Blake2_256(BlakeTwo256::hash(b"%PDF-1.7 diploma"));
+
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(1),
+			2u64,
+			0,
+			None,
+			vec![],
+			None,
+			diploma.clone(),
+			Some(diploma_hash),
+			None
+		));
+
+		let certificate = CertificateById::<Test>::get(cid(0)).unwrap();
+		assert_eq!(certificate.content, diploma);
+		assert_eq!(certificate.content_hash, Some(diploma_hash));
+
+		assert_noop!(
+			CertificateModule::create_certificate(
+				Origin::signed(1),
+				2u64,
+				0,
+				None,
+				vec![],
+				None,
+				Content::IPFS(str2vec("Qm-not-a-cid")),
+				None,
+				None
+			),
+			pallet_utils::Error::<Test>::InvalidIpfsCid
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn anchored_document_should_resolve_to_certificate() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		register_org_with_class(1);
+		let pdf = b"%PDF-1.7 diploma";
+		let sha = DocumentHash::Sha2_256(sp_core::hashing::sha2_256(pdf).into());
+
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(1),
+			2u64,
+			0,
+			None,
+			vec![],
+			None,
+			Content::None,
+			Some(sha),
+			None
+		));
+		assert_eq!(CertificateModule::certificates_by_document(sha), vec![cid(0)]);
+		// The same digest under another algorithm is a different document.
+		let blake = DocumentHash::Blake2_256(sp_core::hashing::blake2_256(pdf).into());
+		assert!(CertificateModule::certificates_by_document(blake).is_empty());
+
+		assert_noop!(
+			CertificateModule::create_certificate(
+				Origin::signed(1),
+				3u64,
+				0,
+				None,
+				vec![],
+				None,
+				Content::None,
+				Some(sha),
+				None
+			),
+			Error::<Test>::DocumentAlreadyAnchored
+		);
+
+		// Another org anchoring the same document does not take it over.
+		let class_id = register_org_with_class(3);
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(3),
+			2u64,
+			class_id,
+			None,
+			vec![],
+			None,
+			Content::None,
+			Some(sha),
+			None
+		));
+		let mut anchors = CertificateModule::certificates_by_document(sha);
+		anchors.sort();
+		let mut expected = vec![cid(0), cid(1)];
+		expected.sort();
+		assert_eq!(anchors, expected);
+
+		// Revoking a certificate releases its anchor, so the org can reissue the document.
+		assert_ok!(CertificateModule::revoke_certificate(Origin::signed(1), cid(0), vec![]));
+		assert_eq!(CertificateModule::certificate_by_document(sha, 1), None);
+		assert_eq!(CertificateModule::certificates_by_document(sha), vec![cid(1)]);
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(1),
+			2u64,
+			0,
+			None,
+			vec![],
+			None,
+			Content::None,
+			Some(sha),
+			None
+		));
+		assert_eq!(CertificateModule::certificate_by_document(sha, 1), Some(cid(2)));
+	});
+}
+
+fn test_leaves(n: u8) -> Vec<H256> {
+	(0..n).map(|i| BlakeTwo256::hash(&[i])).collect()
+}
+
+#[test]
+fn batch_leaf_should_verify_with_merkle_proof() {
+	new_test_ext().execute_with(|| {
+		let leaves = test_leaves(5);
+		let root = merkle::root::<BlakeTwo256>(&leaves).unwrap();
+
+		register_org(1);
+		assert_ok!(CertificateModule::anchor_batch(Origin::signed(1), root, 5, vec![]));
+
+		for (index, leaf) in leaves.iter().enumerate() {
+			let proof = merkle::proof::<BlakeTwo256>(&leaves, index).unwrap();
+			assert_eq!(
+				CertificateModule::verify_batch_leaf(0, *leaf, proof),
+				BatchLeafStatus::Valid
+			);
+		}
+
+		let proof = merkle::proof::<BlakeTwo256>(&leaves, 0).unwrap();
+		assert_eq!(
+			CertificateModule::verify_batch_leaf(0, BlakeTwo256::hash(&[42]), proof.clone()),
+			BatchLeafStatus::NotIncluded
+		);
+		assert_eq!(
+			CertificateModule::verify_batch_leaf(1, leaves[0], proof),
+			BatchLeafStatus::UnknownBatch
+		);
+	});
+}
+
+#[test]
+fn batch_leaf_should_not_verify_inner_node_or_overlong_proof() {
+	new_test_ext().execute_with(|| {
+		let leaves = test_leaves(4);
+		let root = merkle::root::<BlakeTwo256>(&leaves).unwrap();
+		register_org(1);
+		assert_ok!(CertificateModule::anchor_batch(Origin::signed(1), root, 4, vec![]));
+		assert_ok!(CertificateModule::anchor_batch(Origin::signed(1), root, 2, vec![]));
+
+		// the parent of the first two leaves with its sibling is not a leaf of the batch
+		let proof = merkle::proof::<BlakeTwo256>(&leaves, 0).unwrap();
+		let inner = merkle::hash_pair::<BlakeTwo256>(
+			&merkle::hash_leaf::<BlakeTwo256>(&leaves[0]),
+			&proof[0],
+		);
+		assert_eq!(
+			CertificateModule::verify_batch_leaf(0, inner, proof[1..].to_vec()),
+			BatchLeafStatus::NotIncluded
+		);
+
+		// a tree of two leaves has no proof longer than one hash
+		assert_eq!(
+			CertificateModule::verify_batch_leaf(0, leaves[0], proof.clone()),
+			BatchLeafStatus::Valid
+		);
+		assert_eq!(
+			CertificateModule::verify_batch_leaf(1, leaves[0], proof),
+			BatchLeafStatus::NotIncluded
+		);
+	});
+}
+
+#[test]
+fn revoke_batch_leaf_should_work() {
+	new_test_ext().execute_with(|| {
+		let leaves = test_leaves(3);
+		let root = merkle::root::<BlakeTwo256>(&leaves).unwrap();
+		register_org(1);
+		assert_ok!(CertificateModule::anchor_batch(Origin::signed(1), root, 3, vec![]));
+
+		// only the anchoring org can revoke leaves of its batch
+		assert_noop!(
+			CertificateModule::revoke_batch_leaf(Origin::signed(2), 0, leaves[1], vec![]),
+			Error::<Test>::NotBatchIssuer
+		);
+
+		assert_ok!(CertificateModule::revoke_batch_leaf(
+			Origin::signed(1),
+			0,
+			leaves[1],
+			str2vec("issued by mistake")
+		));
+		assert_noop!(
+			CertificateModule::revoke_batch_leaf(Origin::signed(1), 0, leaves[1], vec![]),
+			Error::<Test>::LeafAlreadyRevoked
+		);
+
+		let proof = merkle::proof::<BlakeTwo256>(&leaves, 1).unwrap();
+		assert_eq!(
+			CertificateModule::verify_batch_leaf(0, leaves[1], proof),
+			BatchLeafStatus::Revoked
+		);
+	});
+}
+
+#[test]
+fn anchor_empty_batch_should_fail() {
+	new_test_ext().execute_with(|| {
+		register_org(1);
+		assert_noop!(
+			CertificateModule::anchor_batch(Origin::signed(1), H256::zero(), 0, vec![]),
+			Error::<Test>::EmptyBatch
+		);
+	});
+}
+
+fn payload(org: u64, holder: u64, nonce: u64) -> CertificatePayload<Test> {
+	CertificatePayload {
+		org,
+		terms: CertificateTerms {
+			holder,
+			class_id: 0,
+			grade: Some(80),
+			metadata: str2vec("transcript"),
+			expires: None,
+			content: Content::None,
+			content_hash: None,
+			claims_root: None,
+		},
+		nonce,
+		valid_until: 100,
+	}
+}
+
+#[test]
+fn claim_certificate_should_work() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		let pair = sr25519::Pair::from_seed(&[7; 32]);
+		let key = SigningKey::Sr25519(pair.public());
+		assert_ok!(CertificateModule::add_signing_key(Origin::signed(1), key));
+		assert_ok!(CertificateModule::set_issuance_fee(Origin::signed(1), Some(50)));
+
+		let payload = payload(1, 2, 0);
+		let message = CertificateModule::claim_message(&payload);
+		let signature = MultiSignature::from(pair.sign(&message));
+		assert_ok!(CertificateModule::claim_certificate(
+			Origin::signed(2),
+			payload.clone(),
+			key,
+			signature.clone()
+		));
+
+		let certificate = CertificateModule::certificate_by_id(cid(0)).unwrap();
+		assert_eq!(certificate.org, 1);
+		assert_eq!(certificate.holder, 2);
+		assert_eq!(certificate.depositor, 2);
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Valid);
+		// the holder paid the fee and the deposit, the org nothing
+		assert_eq!(Balances::free_balance(1), 1_050);
+		assert_eq!(Balances::reserved_balance(2), 20);
+		assert_eq!(Balances::free_balance(2), 930);
+
+		// a payload can only be claimed once
+		assert_noop!(
+			CertificateModule::claim_certificate(Origin::signed(2), payload, key, signature),
+			Error::<Test>::ClaimNonceUsed
+		);
+
+		// revocation refunds the holder's deposit but the base deposit
+		assert_ok!(CertificateModule::revoke_certificate(Origin::signed(1), cid(0), vec![]));
+		assert_eq!(Balances::reserved_balance(2), 10);
+	});
+}
+
+#[test]
+fn claim_certificate_should_fail() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		let pair = ed25519::Pair::from_seed(&[7; 32]);
+		let key = SigningKey::Ed25519(pair.public());
+		let payload = payload(1, 2, 0);
+		let message = CertificateModule::claim_message(&payload);
+		let signature = MultiSignature::from(pair.sign(&message));
+
+		// the key must be registered by the org
+		assert_noop!(
+			CertificateModule::claim_certificate(
+				Origin::signed(2),
+				payload.clone(),
+				key,
+				signature.clone()
+			),
+			Error::<Test>::SigningKeyNotFound
+		);
+		assert_ok!(CertificateModule::add_signing_key(Origin::signed(1), key));
+		assert_noop!(
+			CertificateModule::add_signing_key(Origin::signed(1), key),
+			Error::<Test>::SigningKeyAlreadyAdded
+		);
+
+		// only the holder can claim
+		assert_noop!(
+			CertificateModule::claim_certificate(
+				Origin::signed(3),
+				payload.clone(),
+				key,
+				signature.clone()
+			),
+			Error::<Test>::NotCertificateHolder
+		);
+
+		// the signature must cover the submitted payload
+		let mut tampered = payload.clone();
+		tampered.terms.grade = Some(100);
+		assert_noop!(
+			CertificateModule::claim_certificate(
+				Origin::signed(2),
+				tampered,
+				key,
+				signature.clone()
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		// signatures over the bare payload are not bound to this chain
+		assert_noop!(
+			CertificateModule::claim_certificate(
+				Origin::signed(2),
+				payload.clone(),
+				key,
+				MultiSignature::from(pair.sign(&payload.encode()))
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		// expired payloads are rejected
+		Timestamp::set_timestamp(200_000);
+		assert_noop!(
+			CertificateModule::claim_certificate(
+				Origin::signed(2),
+				payload.clone(),
+				key,
+				signature.clone()
+			),
+			Error::<Test>::ClaimExpired
+		);
+
+		// removed keys no longer sign valid payloads
+		Timestamp::set_timestamp(0);
+		assert_ok!(CertificateModule::remove_signing_key(Origin::signed(1), key));
+		assert_noop!(
+			CertificateModule::claim_certificate(Origin::signed(2), payload, key, signature),
+			Error::<Test>::SigningKeyNotFound
+		);
+	});
+}
+
+fn terms(holder: u64) -> CertificateTerms<Test> {
+	CertificateTerms {
+		holder,
+		class_id: 0,
+		grade: None,
+		metadata: vec![],
+		expires: None,
+		content: Content::None,
+		content_hash: None,
+		claims_root: None,
+	}
+}
+
+#[test]
+fn joint_certificate_should_work() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		register_org(3);
+		register_org(4);
+		assert_ok!(CertificateModule::propose_joint_certificate(
+			Origin::signed(1),
+			terms(2),
+			vec![3, 4],
+			100
+		));
+
+		let certificate = CertificateModule::certificate_by_id(cid(0)).unwrap();
+		assert_eq!(certificate.status, CertificateStatus::Pending);
+		assert_eq!(certificate.token, None);
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Pending);
+
+		assert_ok!(CertificateModule::cosign_certificate(Origin::signed(3), cid(0)));
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Pending);
+		assert_noop!(
+			CertificateModule::cosign_certificate(Origin::signed(3), cid(0)),
+			Error::<Test>::AlreadyCosigned
+		);
+
+		// the last signature activates the certificate
+		assert_ok!(CertificateModule::cosign_certificate(Origin::signed(4), cid(0)));
+		let certificate = CertificateModule::certificate_by_id(cid(0)).unwrap();
+		assert_eq!(certificate.status, CertificateStatus::Active);
+		assert_eq!(certificate.token, Some((0, 0)));
+		assert_eq!(<Uniques as Inspect<u64>>::owner(&0, &0), Some(2));
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Valid);
+		System::assert_last_event(crate::mock::Event::CertificateModule(
+			crate::Event::CertificateActivated(cid(0)),
+		));
+
+		// the certificate falls with any of its issuers
+		pallet_sys_man::OrgRevoked::<Test>::insert(4, pallet_sys_man::Org::<Test>::get(4).unwrap());
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::IssuerRevoked);
+	});
+}
+
+#[test]
+fn joint_certificate_should_fail() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		register_org(3);
+
+		for co_issuers in [vec![], vec![1], vec![3, 3]] {
+			assert_noop!(
+				CertificateModule::propose_joint_certificate(
+					Origin::signed(1),
+					terms(2),
+					co_issuers,
+					100
+				),
+				Error::<Test>::InvalidCoIssuers
+			);
+		}
+		assert_noop!(
+			CertificateModule::propose_joint_certificate(Origin::signed(1), terms(2), vec![4], 100),
+			Error::<Test>::NotAnOrganization
+		);
+		assert_noop!(
+			CertificateModule::propose_joint_certificate(Origin::signed(1), terms(2), vec![3], 0),
+			Error::<Test>::InvalidCosignDeadline
+		);
+
+		assert_ok!(CertificateModule::propose_joint_certificate(
+			Origin::signed(1),
+			terms(2),
+			vec![3],
+			100
+		));
+		assert_noop!(
+			CertificateModule::cosign_certificate(Origin::signed(4), cid(0)),
+			Error::<Test>::NotCertificateIssuer
+		);
+
+		Timestamp::set_timestamp(100_000);
+		assert_noop!(
+			CertificateModule::cosign_certificate(Origin::signed(3), cid(0)),
+			Error::<Test>::CosignDeadlinePassed
+		);
+	});
+}
+
+#[test]
+fn joint_certificate_should_be_cancelled() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		register_org(3);
+		let document = DocumentHash::Blake2_256(BlakeTwo256::hash(b"%PDF-1.7 joint diploma"));
+		let joint_terms = CertificateTerms { content_hash: Some(document), ..terms(2) };
+		assert_ok!(CertificateModule::propose_joint_certificate(
+			Origin::signed(1),
+			joint_terms.clone(),
+			vec![3],
+			100
+		));
+		let proposal = cid(0);
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		// only the proposer can cancel before the deadline
+		for who in [2, 3] {
+			assert_noop!(
+				CertificateModule::cancel_joint_certificate(Origin::signed(who), cid(0)),
+				Error::<Test>::CosignDeadlineNotPassed
+			);
+		}
+		assert_ok!(CertificateModule::cancel_joint_certificate(Origin::signed(1), proposal));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(CertificateModule::certificate_by_id(proposal), None);
+		assert_eq!(CertificateModule::certificate_id_by_number(0), None);
+		assert_eq!(CertificateModule::certificate_by_document(document, 1), None);
+		System::assert_last_event(crate::mock::Event::CertificateModule(
+			crate::Event::JointCertificateCancelled(proposal),
+		));
+		assert_noop!(
+			CertificateModule::cancel_joint_certificate(Origin::signed(1), proposal),
+			Error::<Test>::CertificateNotFound
+		);
+
+		// anyone can drop a proposal left unsigned past its deadline
+		assert_ok!(CertificateModule::propose_joint_certificate(
+			Origin::signed(1),
+			joint_terms,
+			vec![3],
+			100
+		));
+		Timestamp::set_timestamp(100_000);
+		assert_ok!(CertificateModule::cancel_joint_certificate(Origin::signed(2), cid(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(CertificateModule::certificate_by_document(document, 1), None);
+
+		// active certificates are revoked instead
+		Timestamp::set_timestamp(0);
+		assert_ok!(CertificateModule::propose_joint_certificate(
+			Origin::signed(1),
+			terms(2),
+			vec![3],
+			100
+		));
+		assert_ok!(CertificateModule::cosign_certificate(Origin::signed(3), cid(2)));
+		assert_noop!(
+			CertificateModule::cancel_joint_certificate(Origin::signed(1), cid(2)),
+			Error::<Test>::InvalidCertificateStatus
+		);
+	});
+}
+
+#[test]
+fn joint_certificate_revocation_should_be_recorded_per_issuer() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		register_org(3);
+		assert_ok!(CertificateModule::propose_joint_certificate(
+			Origin::signed(1),
+			terms(2),
+			vec![3],
+			100
+		));
+		assert_ok!(CertificateModule::cosign_certificate(Origin::signed(3), cid(0)));
+
+		assert_ok!(CertificateModule::revoke_certificate(
+			Origin::signed(3),
+			cid(0),
+			str2vec("program closed")
+		));
+		assert_eq!(
+			CertificateModule::verify(cid(0), 2),
+			Verdict::Revoked(str2vec("program closed"))
+		);
+		assert_noop!(
+			CertificateModule::revoke_certificate(Origin::signed(3), cid(0), vec![]),
+			Error::<Test>::CertificateAlreadyRevoked
+		);
+
+		// the proposer can still record its own revocation
+		assert_ok!(CertificateModule::revoke_certificate(
+			Origin::signed(1),
+			cid(0),
+			str2vec("fraud")
+		));
+		let certificate = CertificateModule::certificate_by_id(cid(0)).unwrap();
+		let revocations: Vec<_> =
+			certificate.revocations().map(|r| (r.revoked.account, r.reason.clone())).collect();
+		assert_eq!(revocations, vec![(1, str2vec("fraud")), (3, str2vec("program closed"))]);
+	});
+}
+
+#[test]
+fn certificate_request_should_be_approved() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		assert_ok!(CertificateModule::set_issuance_fee(Origin::signed(1), Some(50)));
+
+		let evidence = Content::IPFS(str2vec("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"));
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(2), 1, 0, evidence));
+		assert_eq!(CertificateModule::pending_requests(1), vec![0]);
+		// the fee and the request deposit are reserved
+		assert_eq!(Balances::reserved_balance(2), 55);
+
+		// only the addressed org can answer
+		register_org(3);
+		assert_noop!(
+			CertificateModule::approve_request(Origin::signed(3), 0, None, vec![], None),
+			Error::<Test>::NotRequestRecipient
+		);
+
+		assert_ok!(CertificateModule::approve_request(
+			Origin::signed(1),
+			0,
+			Some(90),
+			vec![],
+			None
+		));
+		let request = CertificateModule::request_by_id(0).unwrap();
+		assert_eq!(request.status, ApprovalStatus::Allow);
+		assert_eq!(request.cid, Some(cid(0)));
+		assert!(CertificateModule::pending_requests(1).is_empty());
+		assert_eq!(CertificateModule::verify(cid(0), 2), Verdict::Valid);
+		// the reserved fee went to the org
+		assert_eq!(Balances::free_balance(2), 950);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(1), 1_040);
+
+		assert_noop!(
+			CertificateModule::reject_request(Origin::signed(1), 0, vec![]),
+			Error::<Test>::RequestNotPending
+		);
+	});
+}
+
+#[test]
+fn certificate_request_should_be_rejected_or_cancelled() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		assert_ok!(CertificateModule::set_issuance_fee(Origin::signed(1), Some(50)));
+		assert_noop!(
+			CertificateModule::request_certificate(Origin::signed(2), 1, 1, Content::None),
+			Error::<Test>::ClassNotFound
+		);
+
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(2), 1, 0, Content::None));
+		assert_ok!(CertificateModule::reject_request(
+			Origin::signed(1),
+			0,
+			str2vec("never enrolled")
+		));
+		let request = CertificateModule::request_by_id(0).unwrap();
+		assert_eq!(request.status, ApprovalStatus::Deny);
+		assert_eq!(request.reason, Some(str2vec("never enrolled")));
+		assert_eq!(Balances::free_balance(2), 1_000);
+
+		// the holder can withdraw a request at any time
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(2), 1, 0, Content::None));
+		assert_ok!(CertificateModule::cancel_request(Origin::signed(2), 1));
+		assert_eq!(CertificateModule::request_by_id(1), None);
+		assert_eq!(Balances::free_balance(2), 1_000);
+
+		// others only once it timed out, after which the org can no longer approve it
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(2), 1, 0, Content::None));
+		assert_noop!(
+			CertificateModule::cancel_request(Origin::signed(3), 2),
+			Error::<Test>::RequestNotExpired
+		);
+		Timestamp::set_timestamp(100_000);
+		assert_noop!(
+			CertificateModule::approve_request(Origin::signed(1), 2, None, vec![], None),
+			Error::<Test>::RequestExpired
+		);
+		assert_ok!(CertificateModule::cancel_request(Origin::signed(3), 2));
+		assert!(CertificateModule::pending_requests(1).is_empty());
+		assert_eq!(Balances::free_balance(2), 1_000);
+	});
+}
+
+#[test]
+fn pending_requests_should_be_bounded_per_org() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(2), 1, 0, Content::None));
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(3), 1, 0, Content::None));
+		assert_noop!(
+			CertificateModule::request_certificate(Origin::signed(4), 1, 0, Content::None),
+			Error::<Test>::TooManyPendingRequests
+		);
+
+		// answering a request frees its slot
+		assert_ok!(CertificateModule::reject_request(Origin::signed(1), 0, vec![]));
+		assert_ok!(CertificateModule::request_certificate(Origin::signed(4), 1, 0, Content::None));
+		assert_eq!(CertificateModule::pending_requests(1), vec![1, 2]);
+	});
+}
+
+#[test]
+fn orgs_should_not_request_their_own_certificates() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		assert_noop!(
+			CertificateModule::request_certificate(Origin::signed(1), 1, 0, Content::None),
+			Error::<Test>::SelfRequest
+		);
+	});
+}
+
+#[test]
+fn status_list_should_track_revocation_and_suspension() {
+	new_test_ext().execute_with(|| {
+		register_org_with_class(1);
+		for _ in 0..3 {
+			assert_ok!(CertificateModule::create_certificate(
+				Origin::signed(1),
+				2u64,
+				0,
+				None,
+				vec![],
+				None,
+				Content::None,
+				None,
+				None
+			));
+		}
+		assert_eq!(CertificateModule::certificate_by_id(cid(2)).unwrap().status_index, 2);
+		assert_eq!(CertificateModule::next_status_index(1), 3);
+
+		assert_ok!(CertificateModule::suspend_certificate(Origin::signed(1), cid(1)));
+		assert_eq!(CertificateModule::status_list(1, StatusPurpose::Suspension), vec![0b0100_0000]);
+		assert_ok!(CertificateModule::reinstate_certificate(Origin::signed(1), cid(1)));
+		assert_eq!(CertificateModule::status_list(1, StatusPurpose::Suspension), vec![0]);
+
+		assert_ok!(CertificateModule::revoke_certificate(Origin::signed(1), cid(2), vec![]));
+		assert_eq!(CertificateModule::status_list(1, StatusPurpose::Revocation), vec![0b0010_0000]);
+
+		// revoking a suspended certificate lifts its suspension
+		assert_ok!(CertificateModule::suspend_certificate(Origin::signed(1), cid(0)));
+		assert_ok!(CertificateModule::revoke_certificate(Origin::signed(1), cid(0), vec![]));
+		assert_eq!(CertificateModule::status_list(1, StatusPurpose::Revocation), vec![0b1010_0000]);
+		assert_eq!(CertificateModule::status_list(1, StatusPurpose::Suspension), vec![0]);
+
+		// indices are assigned per org
+		let class_id = register_org_with_class(3);
+		assert_ok!(CertificateModule::create_certificate(
+			Origin::signed(3),
+			2u64,
+			class_id,
+			None,
+			vec![],
+			None,
+			Content::None,
+			None,
+			None
+		));
+		assert_eq!(CertificateModule::certificate_by_id(cid(3)).unwrap().status_index, 0);
+		assert!(CertificateModule::status_list(3, StatusPurpose::Revocation).is_empty());
+	});
+}
+
+#[test]
+fn migration_to_v1_should_rekey_certificates() {
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		StorageHasher, Twox64Concat,
+	};
+
+	new_test_ext().execute_with(|| {
+		let legacy_key = |number: u32| {
+			let mut key = storage_prefix(b"CertificateModule", b"CertificateById").to_vec();
+			key.extend(Twox64Concat::hash(&number.encode()));
+			key
+		};
+		// certificates as stored before hash ids, `{ cid: u32, org, scrore: u32, metadata }`
+		for number in 0..8u32 {
+			let legacy = (number, 1u64, 5u32, str2vec("BSc")).encode();
+			unhashed::put_raw(&legacy_key(number), &legacy);
+		}
+		unhashed::put_raw(&legacy_key(8), &(8u32, 3u64, 5u32, str2vec("MSc")).encode());
+		// and an entry that does not decode as one
+		unhashed::put_raw(&legacy_key(9), &[1, 2, 3]);
+		StorageVersion::new(0).put::<CertificateModule>();
+
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		let id = pallet_utils::Pallet::<Test>::derive_id(b"certificate", &1, 1);
+		assert_eq!(CertificateModule::certificate_id_by_number(1), Some(id));
+		let certificate = CertificateModule::certificate_by_id(id).unwrap();
+		assert_eq!(certificate.number, 1);
+		assert_eq!((certificate.org, certificate.holder), (1, 1));
+		assert_eq!(certificate.score, 5);
+		assert_eq!(certificate.metadata, str2vec("BSc"));
+		assert_eq!(certificate.status, CertificateStatus::Active);
+		assert_eq!(certificate.deposit, 0);
+		assert_eq!(unhashed::get_raw(&legacy_key(1)), None);
+
+		// status list bits follow the legacy numbers of each org, whatever the storage order
+		for number in 0..8 {
+			let certificate = CertificateModule::certificate_by_id(cid(number)).unwrap();
+			assert_eq!(certificate.status_index, number);
+		}
+		assert_eq!(CertificateModule::next_status_index(1), 8);
+		let other = CertificateModule::certificate_by_id(cid(8)).unwrap();
+		assert_eq!((other.org, other.status_index), (3, 0));
+
+		// each org gets a class of its own for its legacy certificates
+		let class = CertificateModule::class_by_id(certificate.class_id).unwrap();
+		assert_eq!(class.org, 1);
+		assert_eq!(class.name, str2vec("Legacy certificates"));
+		assert_eq!(CertificateModule::certificate_by_id(cid(0)).unwrap().class_id, class.class_id);
+		let other_class = CertificateModule::class_by_id(other.class_id).unwrap();
+		assert_eq!(other_class.org, 3);
+		assert_eq!(CertificateModule::class_id(), 2);
+
+		// entries that do not decode are kept
+		assert_eq!(CertificateModule::certificate_id_by_number(9), None);
+		assert_eq!(unhashed::get_raw(&legacy_key(9)), Some(vec![1, 2, 3]));
+		assert_eq!(CertificateModule::on_chain_storage_version(), 1);
 	});
 }
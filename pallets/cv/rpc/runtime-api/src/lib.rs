@@ -0,0 +1,43 @@
+//! Runtime API definition for the CV pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_cv::{scoring::CvScore, EffectiveStatus, ItemFilter};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait CvApi<AccountId, Item, ItemVersion, ItemTombstone, Hash> where
+		AccountId: Codec,
+		Item: Codec,
+		ItemVersion: Codec,
+		ItemTombstone: Codec,
+		Hash: Codec,
+	{
+		/// Returns the item stored under `item_id`, if any.
+		fn item(item_id: Hash) -> Option<Item>;
+
+		/// Returns the status of item `item_id` right now, reporting expired and revoked items as
+		/// such.
+		fn item_status(item_id: Hash) -> Option<EffectiveStatus>;
+
+		/// Returns the versions of item `item_id`, oldest first, the last one being current.
+		fn item_versions(item_id: Hash) -> Vec<ItemVersion>;
+
+		/// Returns who revoked item `item_id`, when and why, if its owner withdrew it.
+		fn item_tombstone(item_id: Hash) -> Option<ItemTombstone>;
+
+		/// Returns whether `viewer` may see item `item_id` right now, as its owner or through an
+		/// unexpired access grant.
+		fn can_view(viewer: AccountId, item_id: Hash) -> bool;
+
+		/// Returns the content key of encrypted item `item_id` sealed to `reader`, if any.
+		fn item_key(item_id: Hash, reader: AccountId) -> Option<Vec<u8>>;
+
+		/// Returns the items in the CV of `account` matching `filter`.
+		fn items(account: AccountId, filter: ItemFilter) -> Vec<Item>;
+
+		/// Returns the score of the CV of `account`, with the contribution of each item.
+		fn score(account: AccountId) -> CvScore<Hash>;
+	}
+}
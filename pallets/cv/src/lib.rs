@@ -5,6 +5,9 @@
 /// <https://docs.substrate.io/v3/runtime/frame>
 pub use pallet::*;
 
+pub mod migrations;
+pub mod scoring;
+
 #[cfg(test)]
 mod mock;
 
@@ -13,48 +16,117 @@ mod tests;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{ReservableCurrency, StorageVersion},
+		transactional,
+	};
 	use frame_system::pallet_prelude::*;
-	use pallet_utils::{String, TypeID, UnixEpoch, WhoAndWhen};
+	use pallet_certificate::{Certificate, ClassKind, OnCertificateRevoked, Verdict};
+	use crate::scoring::{CvScore, ItemScore, ItemScoreContext, ScoringPolicy};
+	use pallet_utils::{BalanceOf, Content, String, TypeID, UnixEpoch, WhoAndWhen};
 	use scale_info::TypeInfo;
+	use sp_runtime::traits::{Hash, UniqueSaturatedInto};
+	use frame_support::sp_std::collections::btree_set::BTreeSet;
 	use frame_support::inherent::Vec;
 
 	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 	#[scale_info(bounds(), skip_type_params(T))]
 	pub struct Item<T: Config> {
-		item_id: TypeID,
-		user_id: T::AccountId,
-		created: WhoAndWhen<T>,
-		org_date: Option<UnixEpoch>,
-		exp_date: Option<UnixEpoch>,
-		certificate_id: Option<TypeID>,
-		score: u32,
-		metadata: String,
+		pub item_id: T::Hash,
+		/// Sequential number the item was known by before hash-based ids.
+		pub number: TypeID,
+		/// Account the item is about, whose CV it belongs to.
+		pub user_id: T::AccountId,
+		/// Account that made the claim, see [`Item::issuer`].
+		pub created: WhoAndWhen<T>,
+		/// When the subject took the item into their CV. Items claimed by the subject themselves
+		/// are accepted on creation, others wait in the subject's inbox.
+		pub accepted: Option<WhoAndWhen<T>>,
+		/// Structured fields, `None` for items created before items were typed.
+		pub fields: Option<ItemFields>,
+		pub org_date: Option<UnixEpoch>,
+		pub exp_date: Option<UnixEpoch>,
+		pub certificate_id: Option<T::Hash>,
+		pub score: u32,
+		/// Plaintext, or ChaCha20-Poly1305 ciphertext under a content key sealed to each reader
+		/// in [`ItemKeys`].
+		pub metadata: String,
+		pub encrypted: bool,
+		pub content: Content,
+		pub content_hash: Option<T::Hash>,
+		/// Number of times the item was amended, the index of its current version in
+		/// [`ItemVersions`].
+		pub version: u32,
 	}
 
 	impl<T: Config> Item<T> {
 		pub fn new(
-			id: TypeID,
+			id: T::Hash,
+			number: TypeID,
 			user_id: T::AccountId,
 			created_by: T::AccountId,
+			fields: Option<ItemFields>,
 			org_date: Option<UnixEpoch>,
 			exp_date: Option<UnixEpoch>,
-			certificate_id: Option<TypeID>,
+			certificate_id: Option<T::Hash>,
 			score: u32,
 			metadata: String,
+			encrypted: bool,
+			content: Content,
+			content_hash: Option<T::Hash>,
 		) -> Self {
+			let accepted = if user_id == created_by {
+				Some(WhoAndWhen::<T>::new(created_by.clone()))
+			} else {
+				None
+			};
 			Item {
 				item_id: id,
+				number,
 				user_id,
 				created: WhoAndWhen::<T>::new(created_by.clone()),
+				accepted,
+				fields,
 				org_date,
 				exp_date,
 				certificate_id,
 				score,
 				metadata,
+				encrypted,
+				content,
+				content_hash,
+				version: 0,
 			}
 		}
 
+		/// Whether the item is past its expiry date at unix time `now`.
+		pub fn is_expired(&self, now: UnixEpoch) -> bool {
+			self.exp_date.map_or(false, |exp_date| exp_date <= now)
+		}
+
+		/// Last time, up to `now`, the item was current: the end of its period, `now` for ongoing
+		/// ones, otherwise the date of a publication or award, its issue date or its creation.
+		pub fn last_active(&self, now: UnixEpoch) -> UnixEpoch {
+			let fields_date = self.fields.as_ref().and_then(|f| match (f.kind, f.start, f.end) {
+				(_, _, Some(end)) => Some(end),
+				(ItemKind::Publication | ItemKind::Award, start, None) => start,
+				(_, Some(_), None) => Some(now),
+				_ => None,
+			});
+			let created: u64 = self.created.time.unique_saturated_into();
+			fields_date.or(self.org_date).unwrap_or(created / 1000).min(now)
+		}
+
+		/// Account that made the claim, either the subject or e.g. an employer.
+		pub fn issuer(&self) -> &T::AccountId {
+			&self.created.account
+		}
+
+		pub fn is_accepted(&self) -> bool {
+			self.accepted.is_some()
+		}
+
 		// pub fn ensure_owner(&self, account: &T::AccountId) -> DispatchResult {
 		// 	ensure!(self.is_owner(account), Error::<T>::NotAPostOwner);
 		// 	Ok(())
@@ -65,27 +137,207 @@ pub mod pallet {
 		// }
 	}
 
-	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
-	pub enum Status {
+	pub use pallet_utils::ApprovalStatus as Status;
+
+	/// Status of an item as seen by verifiers: its review status, unless it has expired or was
+	/// revoked by its owner.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum EffectiveStatus {
 		Pending,
 		Allow,
 		Deny,
+		Expired,
+		Revoked,
+	}
+
+	/// Content key of an encrypted item sealed to one reader.
+	pub type Envelope = Vec<u8>;
+
+	/// Length of an [`Envelope`]: an ephemeral x25519 public key followed by the 32-byte content
+	/// key encrypted with ChaCha20-Poly1305 under the key agreed with the reader.
+	pub const ENVELOPE_LENGTH: usize = 80;
+
+	/// Category of a CV item.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum ItemKind {
+		Education,
+		Employment,
+		Skill,
+		Project,
+		Publication,
+		Award,
+		Language,
+		Volunteer,
+	}
+
+	/// Highest skill level, from beginner (1) to expert.
+	pub const MAX_SKILL_LEVEL: u8 = 5;
+	/// Highest language level, CEFR A1 (1) to C2.
+	pub const MAX_LANGUAGE_LEVEL: u8 = 6;
+
+	/// Structured fields of a CV item. Which fields a kind takes is checked on creation:
+	///
+	/// - education, employment and volunteering need an institution and may have a period;
+	/// - projects, publications and awards may name an institution, e.g. a publisher, and have
+	///   a period or, for publications and awards, a single `start` date;
+	/// - skills and languages need a level and take neither institution nor dates.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ItemFields {
+		pub kind: ItemKind,
+		/// School, employer, publisher, awarding body or organization volunteered for.
+		pub institution: Option<String>,
+		/// Degree, position, skill, project, publication, award or language.
+		pub title: String,
+		pub start: Option<UnixEpoch>,
+		pub end: Option<UnixEpoch>,
+		/// Proficiency of a skill or language.
+		pub level: Option<u8>,
+	}
+
+	/// Criteria an employer filters CV items by. Empty criteria match every item.
+	#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct ItemFilter {
+		pub kind: Option<ItemKind>,
+		/// Exact institution.
+		pub institution: Option<String>,
+		/// Case-sensitive substring of the title.
+		pub title: Option<String>,
+		pub min_level: Option<u8>,
+		/// Matches items whose period covers this time, e.g. current positions.
+		pub active_at: Option<UnixEpoch>,
+	}
+
+	impl ItemFilter {
+		pub fn matches(&self, fields: &ItemFields) -> bool {
+			let contains = |haystack: &[u8], needle: &[u8]| {
+				needle.is_empty() || haystack.windows(needle.len()).any(|w| w == needle)
+			};
+			let active = |at: UnixEpoch| {
+				fields.start.map_or(true, |start| start <= at) &&
+					fields.end.map_or(true, |end| at < end)
+			};
+			self.kind.map_or(true, |kind| kind == fields.kind) &&
+				(self.institution.is_none() || self.institution == fields.institution) &&
+				self.title.as_ref().map_or(true, |title| contains(&fields.title, title)) &&
+				self.min_level.map_or(true, |min| fields.level.map_or(false, |l| l >= min)) &&
+				self.active_at.map_or(true, active)
+		}
+	}
+
+	/// Review of an item by the org responsible for vouching for it.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct Review<T: Config> {
+		/// The issuer of the linked certificate, or the org the owner named.
+		pub verifier: T::AccountId,
+		pub requested: WhoAndWhen<T>,
+		/// When the verifier decided, `None` while the review is pending.
+		pub decided: Option<WhoAndWhen<T>>,
+		pub reason: Option<String>,
 	}
-	impl Default for Status {
-		fn default() -> Self {
-            Self::Pending
-        }
+
+	/// Items of a CV an access grant covers.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum AccessScope<Hash> {
+		/// A single item.
+		Item(Hash),
+		/// Every item of a kind, including those added after the grant.
+		Kind(ItemKind),
+	}
+
+	/// Access of a viewer, e.g. a recruiter, to part of a CV.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct AccessGrant<T: Config> {
+		pub granted: WhoAndWhen<T>,
+		/// First block the grant no longer applies at.
+		pub expires: T::BlockNumber,
+	}
+
+	/// What remains of an item its owner revoked, so those who saw it can tell it was withdrawn.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct ItemTombstone<T: Config> {
+		/// Account whose CV the item was in.
+		pub user_id: T::AccountId,
+		/// Status of the item when it was revoked.
+		pub status: Status,
+		pub revoked: WhoAndWhen<T>,
+		pub reason: String,
+	}
+
+	/// A version of an item, recorded on creation and on each amendment.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(bounds(), skip_type_params(T))]
+	pub struct ItemVersion<T: Config> {
+		/// Hash of the item metadata, so a copy kept off-chain can be matched to its version.
+		pub metadata_hash: T::Hash,
+		pub fields: Option<ItemFields>,
+		pub org_date: Option<UnixEpoch>,
+		pub exp_date: Option<UnixEpoch>,
+		/// Who created or amended the item, and when.
+		pub changed: WhoAndWhen<T>,
+		/// What the amendment changed, empty for the original version.
+		pub note: String,
+	}
+
+	impl<T: Config> ItemVersion<T> {
+		/// The version `item` is currently at.
+		pub fn of(item: &Item<T>, changed: WhoAndWhen<T>, note: String) -> Self {
+			ItemVersion {
+				metadata_hash: T::Hashing::hash(&item.metadata),
+				fields: item.fields.clone(),
+				org_date: item.org_date,
+				exp_date: item.exp_date,
+				changed,
+				note,
+			}
+		}
 	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config + pallet_utils::Config {
+	pub trait Config:
+		frame_system::Config
+		+ pallet_utils::Config
+		+ pallet_account::Config
+		+ pallet_certificate::Config
+	{
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Maximum number of items in a CV.
+		#[pallet::constant]
+		type MaxItems: Get<u32>;
+		/// Maximum number of items about an account awaiting its consent.
+		#[pallet::constant]
+		type MaxPendingItems: Get<u32>;
+		/// Amount reserved from the issuer of an item about someone else until the subject
+		/// decides on it. Refunded on acceptance or withdrawal, slashed on rejection.
+		#[pallet::constant]
+		type ProposalDeposit: Get<BalanceOf<Self>>;
+		/// Maximum length in bytes of the text fields of an item.
+		#[pallet::constant]
+		type MaxFieldLength: Get<u32>;
+		/// How far ahead of now, in seconds, the dates of an item may lie.
+		#[pallet::constant]
+		type MaxFutureDate: Get<UnixEpoch>;
+		/// Maximum number of readers the content key of an encrypted item is sealed to.
+		#[pallet::constant]
+		type MaxItemKeys: Get<u32>;
+		/// Maximum number of access grants an account has given at a time, expired ones
+		/// included until revoked.
+		#[pallet::constant]
+		type MaxAccessGrants: Get<u32>;
+		/// Decides what verified items add to the score of a CV.
+		type CvScoringPolicy: ScoringPolicy;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
@@ -94,29 +346,115 @@ pub mod pallet {
 	#[pallet::getter(fn item_id)]
 	// Learn more about declaring storage items:
 	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
+	/// Number of the next item, used as the nonce of its id.
 	pub type ItemId<T> = StorageValue<_, u32, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn item_by_id)]
-	pub type ItemById<T> = StorageMap<_, Twox64Concat, TypeID, Item<T>, OptionQuery>;
+	pub type ItemById<T: Config> = StorageMap<_, Identity, T::Hash, Item<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn item_versions)]
+	/// Versions of an item, oldest first, the last one being current.
+	pub type ItemVersions<T: Config> =
+		StorageMap<_, Identity, T::Hash, Vec<ItemVersion<T>>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn item_id_by_number)]
+	/// Id of an item by its sequential number, for lookups by legacy numeric id.
+	pub type ItemIdByNumber<T: Config> = StorageMap<_, Twox64Concat, TypeID, T::Hash, OptionQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn item_status_by_item_id)]
-	pub type ItemStatusByItemId<T> = StorageMap<_, Twox64Concat, TypeID, Status, ValueQuery>;
+	pub type ItemStatusByItemId<T: Config> =
+		StorageMap<_, Identity, T::Hash, Status, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_by_item_id)]
+	/// Latest review of an item, backing its status in [`ItemStatusByItemId`].
+	pub type ReviewByItemId<T: Config> = StorageMap<_, Identity, T::Hash, Review<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn item_key)]
+	/// Content key of an encrypted item, sealed to the x25519 key each reader registered in
+	/// `pallet_account`.
+	pub type ItemKeys<T: Config> =
+		StorageDoubleMap<_, Identity, T::Hash, Twox64Concat, T::AccountId, Envelope, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tombstone_by_item_id)]
+	/// Items revoked by their owner. Their versions are kept in [`ItemVersions`].
+	pub type TombstoneByItemId<T: Config> =
+		StorageMap<_, Identity, T::Hash, ItemTombstone<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn items_by_certificate)]
+	/// Items backed by a certificate, to be invalidated when it gets revoked.
+	pub type ItemsByCertificate<T: Config> =
+		StorageMap<_, Identity, T::Hash, Vec<T::Hash>, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn items_by_accountid)]
 	pub type ItemsByAccountId<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, Vec<TypeID>, ValueQuery>;
+		StorageMap<_, Twox64Concat, T::AccountId, Vec<T::Hash>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_items_by_accountid)]
+	/// Items others created about an account, awaiting its consent.
+	pub type PendingItemsByAccountId<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Vec<T::Hash>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_deposit)]
+	/// Deposit reserved from the issuer of a pending item, see [`Config::ProposalDeposit`].
+	pub type ProposalDeposits<T: Config> =
+		StorageMap<_, Identity, T::Hash, BalanceOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn access_grant)]
+	/// Access an account gave a viewer to part of its CV, by owner and then viewer and scope.
+	pub type AccessGrants<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(T::AccountId, AccessScope<T::Hash>),
+		AccessGrant<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn access_grant_count)]
+	/// Number of entries of an account in [`AccessGrants`], bounded by `MaxAccessGrants`.
+	pub type AccessGrantCount<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		RevokeSucceed(TypeID),
-		CreateSucceed(TypeID),
-		SetStatusSucceed(TypeID),
+		RevokeSucceed(T::Hash),
+		CreateSucceed(T::Hash),
+		SetStatusSucceed(T::Hash),
+		/// An item about someone else awaits their consent. [issuer, subject, item_id]
+		ItemProposed(T::AccountId, T::AccountId, T::Hash),
+		/// The subject took a proposed item into their CV. [item_id]
+		ItemAccepted(T::Hash),
+		/// The subject turned down a proposed item. [item_id]
+		ItemRejected(T::Hash),
+		/// The issuer took back a proposed item before the subject decided on it. [item_id]
+		ProposalWithdrawn(T::Hash),
+		/// The owner asked an org to review an item. [item_id, verifier]
+		ReviewRequested(T::Hash, T::AccountId),
+		/// The owner amended an item. [item_id, version]
+		ItemAmended(T::Hash, u32),
+		/// An account let a viewer see part of its CV. [owner, viewer, scope, expires]
+		AccessGranted(T::AccountId, T::AccountId, AccessScope<T::Hash>, T::BlockNumber),
+		/// An account withdrew the access of a viewer. [owner, viewer, scope]
+		AccessRevoked(T::AccountId, T::AccountId, AccessScope<T::Hash>),
+		/// The owner sealed the content key of an item to a viewer. [item_id, viewer]
+		ItemKeyShared(T::Hash, T::AccountId),
 	}
 
 	// Errors inform users that something went wrong.
@@ -126,6 +464,78 @@ pub mod pallet {
 		ItemNotFound,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// Item is not awaiting the consent of the caller.
+		ItemNotPending,
+		/// Subject has too many items awaiting consent.
+		TooManyPendingItems,
+		/// CV already holds `MaxItems` items.
+		TooManyItems,
+		/// Account already gave `MaxAccessGrants` access grants.
+		TooManyAccessGrants,
+		/// Only the subject of an accepted item can do this.
+		NotItemOwner,
+		/// Only the account that proposed the item can do this.
+		NotItemIssuer,
+		/// Verifier is not a registered organization.
+		NotAnOrganization,
+		/// Item has no linked certificate, so the owner has to name a verifier.
+		VerifierRequired,
+		/// Items backed by a certificate are reviewed by its issuer, denied items by the org that
+		/// denied them.
+		InvalidVerifier,
+		/// Linked certificate does not exist.
+		CertificateNotFound,
+		/// Subject already has an item backed by the linked certificate.
+		CertificateAlreadyLinked,
+		/// Item is already awaiting a review.
+		ReviewPending,
+		/// Item has no pending review.
+		ReviewNotPending,
+		/// Only the org responsible for the item can review it.
+		NotItemVerifier,
+		/// A review must allow or deny the item.
+		InvalidDecision,
+		/// Linked certificate was issued to someone else than the item subject.
+		NotCertificateHolder,
+		/// Linked certificate has been revoked.
+		CertificateRevoked,
+		/// Linked certificate has expired.
+		CertificateExpired,
+		/// Linked certificate is suspended or still awaiting co-signatures.
+		CertificateNotActive,
+		/// A text field of the item is empty or longer than `MaxFieldLength`.
+		InvalidFieldLength,
+		/// The item kind requires a field that was left out.
+		MissingField,
+		/// The item kind does not take one of the fields given.
+		UnexpectedField,
+		/// Level is out of the range of the item kind.
+		InvalidLevel,
+		/// Item period ends before it starts.
+		InvalidPeriod,
+		/// Item expires before it was issued.
+		InvalidExpiry,
+		/// A date of the item lies further ahead than `MaxFutureDate`.
+		DateTooFarInFuture,
+		/// Access grants must expire after the current block.
+		GrantExpired,
+		/// Caller gave the viewer no such access.
+		GrantNotFound,
+		/// Content key of the item is sealed to more than `MaxItemKeys` readers.
+		TooManyItemKeys,
+		/// Envelope is not `ENVELOPE_LENGTH` bytes long.
+		InvalidEnvelope,
+		/// Reader has not registered an encryption key in `pallet_account`.
+		EncryptionKeyNotRegistered,
+		/// Only the subject and the issuer can read an item from its creation, others need
+		/// access first.
+		InvalidRecipient,
+		/// The subject must be able to read its encrypted items.
+		SubjectKeyRequired,
+		/// Item metadata is not encrypted.
+		ItemNotEncrypted,
+		/// Viewer has no access to the item.
+		NoAccess,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -133,78 +543,740 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
+		/// Adds an item about `subject`. An item about the caller joins their CV right away, one
+		/// about someone else waits in their inbox until they accept it.
+		///
+		/// An item backed by a valid certificate of `subject` is allowed on behalf of its issuer if
+		/// its kind and title match the certificate class, otherwise it awaits the issuer's review.
+		/// A certificate backs at most one item of its holder.
+		///
+		/// Proposing an item about someone else reserves `ProposalDeposit` from the caller.
 		#[pallet::weight(10_000)]
+		#[transactional]
 		pub fn create_item(
 			origin: OriginFor<T>,
-			_account_id: T::AccountId,
+			subject: T::AccountId,
+			fields: ItemFields,
 			_metadata: String,
 			_org_date: Option<UnixEpoch>,
 			_exp_date: Option<UnixEpoch>,
-			_certificated_id: Option<TypeID>,
+			_certificated_id: Option<T::Hash>,
+			content: Content,
+			content_hash: Option<T::Hash>,
+			envelopes: Vec<(T::AccountId, Envelope)>,
 		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
-			let item_id = Self::item_id();
+			<pallet_utils::Pallet<T>>::ensure_content_is_valid(&content)?;
+			Self::ensure_fields_are_valid(&fields)?;
+			Self::ensure_dates_are_valid(&fields, _org_date, _exp_date)?;
+			Self::ensure_envelopes_are_valid(&subject, &who, &envelopes)?;
+			if subject == who {
+				Self::ensure_cv_has_room(&who)?;
+			}
+			let certificate = match _certificated_id {
+				Some(cid) => {
+					let certificate = Self::ensure_certificate_is_valid(cid, &subject)?;
+					ensure!(
+						Self::items_by_certificate(cid)
+							.into_iter()
+							.filter_map(Self::item_by_id)
+							.all(|item| item.user_id != subject),
+						Error::<T>::CertificateAlreadyLinked
+					);
+					let matches = Self::matches_certificate(&fields, &certificate);
+					Some((certificate, matches))
+				},
+				None => None,
+			};
+			let number = Self::item_id();
+			let next_number = number.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			let item_id = <pallet_utils::Pallet<T>>::derive_id(b"item", &who, number);
 			let new_item: Item<T> = Item::new(
 				item_id,
-				_account_id.clone(),
+				number,
+				subject.clone(),
 				who.clone(),
+				Some(fields),
 				_org_date,
 				_exp_date,
 				_certificated_id,
 				0,
 				_metadata,
+				!envelopes.is_empty(),
+				content,
+				content_hash,
+			);
+			if subject == who {
+				<ItemsByAccountId<T>>::mutate(&subject, |x| x.push(item_id));
+			} else {
+				<PendingItemsByAccountId<T>>::try_mutate(&subject, |x| -> DispatchResult {
+					ensure!(
+						x.len() < T::MaxPendingItems::get() as usize,
+						Error::<T>::TooManyPendingItems
+					);
+					x.push(item_id);
+					Ok(())
+				})?;
+				let deposit = T::ProposalDeposit::get();
+				T::Currency::reserve(&who, deposit)?;
+				<ProposalDeposits<T>>::insert(item_id, deposit);
+			}
+			<ItemVersions<T>>::append(
+				item_id,
+				ItemVersion::of(&new_item, new_item.created.clone(), Vec::new()),
 			);
 			<ItemById<T>>::insert(item_id, new_item);
-			<ItemsByAccountId<T>>::mutate(who, |x| x.push(item_id));
-			<ItemId<T>>::mutate(|n| {
-				*n += 1;
-			});
+			for (reader, envelope) in envelopes {
+				<ItemKeys<T>>::insert(item_id, reader, envelope);
+			}
+			<ItemIdByNumber<T>>::insert(number, item_id);
+			<ItemId<T>>::put(next_number);
+			if let Some((certificate, matches)) = certificate {
+				<ItemsByCertificate<T>>::append(certificate.cid, item_id);
+				<ReviewByItemId<T>>::insert(item_id, Review {
+					verifier: certificate.org.clone(),
+					requested: WhoAndWhen::<T>::new(who.clone()),
+					decided: matches.then(|| WhoAndWhen::<T>::new(certificate.org.clone())),
+					reason: None,
+				});
+				if matches {
+					<ItemStatusByItemId<T>>::insert(item_id, Status::Allow);
+				} else {
+					Self::deposit_event(Event::ReviewRequested(item_id, certificate.org));
+				}
+			}
 			// Emit an event.
 			Self::deposit_event(Event::CreateSucceed(item_id));
+			if subject != who {
+				Self::deposit_event(Event::ItemProposed(who, subject, item_id));
+			}
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
 
-		#[pallet::weight(10_000)]
-		pub fn revoke_item(origin: OriginFor<T>, _item_id: TypeID) -> DispatchResult {
+		/// Withdraws item `item_id` from the caller's CV, leaving a tombstone with `reason` in
+		/// its place. Access granted to the item alone ends with it.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(
+				Weight::from(T::MaxAccessGrants::get()),
+				2 * Weight::from(T::MaxAccessGrants::get()),
+			)
+		)]
+		pub fn revoke_item(
+			origin: OriginFor<T>,
+			_item_id: T::Hash,
+			reason: String,
+		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
+			ensure!(
+				reason.len() <= T::MaxFieldLength::get() as usize,
+				Error::<T>::InvalidFieldLength
+			);
 
-			let item_idx = Self::items_by_accountid(&who).iter().position(|x| *x == _item_id);
-			ensure!(item_idx != None, Error::<T>::ItemNotFound);
-			if let Some(iid) = item_idx {
-				<ItemsByAccountId<T>>::mutate(&who, |x| x.swap_remove(iid));
+			// Keep the order of the remaining items, the CV lists them as entered.
+			<ItemsByAccountId<T>>::try_mutate(&who, |x| -> DispatchResult {
+				let index =
+					x.iter().position(|id| *id == _item_id).ok_or(Error::<T>::ItemNotFound)?;
+				x.remove(index);
+				Ok(())
+			})?;
+			if let Some(item) = <ItemById<T>>::take(_item_id) {
+				<ItemIdByNumber<T>>::remove(item.number);
+				Self::unlink_certificate(&item);
+			}
+			<ReviewByItemId<T>>::remove(_item_id);
+			let _ = <ItemKeys<T>>::remove_prefix(_item_id, None);
+			let scope = AccessScope::Item(_item_id);
+			let viewers: Vec<_> = <AccessGrants<T>>::iter_key_prefix(&who)
+				.filter(|(_, granted)| *granted == scope)
+				.map(|(viewer, _)| viewer)
+				.collect();
+			for viewer in viewers {
+				<AccessGrants<T>>::remove(&who, (viewer.clone(), scope));
+				<AccessGrantCount<T>>::mutate(&who, |count| *count = count.saturating_sub(1));
+				Self::deposit_event(Event::AccessRevoked(who.clone(), viewer, scope));
 			}
-			<ItemById<T>>::remove(_item_id);
+			<TombstoneByItemId<T>>::insert(_item_id, ItemTombstone {
+				user_id: who.clone(),
+				status: <ItemStatusByItemId<T>>::take(_item_id),
+				revoked: WhoAndWhen::<T>::new(who),
+				reason,
+			});
 			// Emit an event.
 			Self::deposit_event(Event::RevokeSucceed(_item_id));
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
 
-		#[pallet::weight(1000)]
-		pub fn set_status_item(origin: OriginFor<T>, _item_id: TypeID, status: Status) -> DispatchResult {
+		/// Amends item `item_id` of the caller's CV, keeping its earlier versions. `note` says
+		/// what changed. A reviewed item goes back to its verifier, pending a new review.
+		///
+		/// `envelopes` replace the content keys of the item, which is stored in plaintext if
+		/// there are none. Viewers the previous content key was shared with need new envelopes.
+		#[pallet::weight(10_000)]
+		pub fn amend_item(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			fields: ItemFields,
+			metadata: String,
+			org_date: Option<UnixEpoch>,
+			exp_date: Option<UnixEpoch>,
+			note: String,
+			envelopes: Vec<(T::AccountId, Envelope)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				note.len() <= T::MaxFieldLength::get() as usize,
+				Error::<T>::InvalidFieldLength
+			);
+			Self::ensure_fields_are_valid(&fields)?;
+			Self::ensure_dates_are_valid(&fields, org_date, exp_date)?;
+
+			let version = <ItemById<T>>::try_mutate(item_id, |item| -> Result<_, DispatchError> {
+				let item = item.as_mut().ok_or(Error::<T>::ItemNotFound)?;
+				ensure!(item.user_id == who && item.is_accepted(), Error::<T>::NotItemOwner);
+				// Items backed by a revoked certificate stay denied.
+				if let Some(cid) = item.certificate_id {
+					Self::ensure_certificate_is_valid(cid, &who)?;
+				}
+				Self::ensure_envelopes_are_valid(&who, item.issuer(), &envelopes)?;
+				item.version = item.version.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+				item.fields = Some(fields);
+				item.org_date = org_date;
+				item.exp_date = exp_date;
+				item.metadata = metadata;
+				item.encrypted = !envelopes.is_empty();
+				let _ = <ItemKeys<T>>::remove_prefix(item_id, None);
+				for (reader, envelope) in envelopes {
+					<ItemKeys<T>>::insert(item_id, reader, envelope);
+				}
+				<ItemVersions<T>>::append(
+					item_id,
+					ItemVersion::of(item, WhoAndWhen::<T>::new(who.clone()), note),
+				);
+				Ok(item.version)
+			})?;
+
+			if let Some(mut review) = Self::review_by_item_id(item_id) {
+				let verifier = review.verifier.clone();
+				review.requested = WhoAndWhen::<T>::new(who);
+				review.decided = None;
+				review.reason = None;
+				<ReviewByItemId<T>>::insert(item_id, review);
+				<ItemStatusByItemId<T>>::insert(item_id, Status::Pending);
+				Self::deposit_event(Event::ReviewRequested(item_id, verifier));
+			}
+			Self::deposit_event(Event::ItemAmended(item_id, version));
+			Ok(())
+		}
+
+		/// Asks the org responsible for item `item_id` to review it: the issuer of its linked
+		/// certificate, or `verifier` for items without one. Also asks for a re-review of an item
+		/// that was already decided, which for a denied item goes to the org that denied it.
+		///
+		/// Items whose certificate is no longer valid cannot be reviewed again, so a denial
+		/// recorded on revocation stands.
+		#[pallet::weight(10_000)]
+		pub fn request_review(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			verifier: Option<T::AccountId>,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let item_idx = Self::items_by_accountid(&who).iter()
-			.position(|x| { *x == _item_id });
-			ensure!(item_idx != None, Error::<T>::ItemNotFound);
-			match <ItemStatusByItemId<T>>::contains_key(_item_id) {
-				true => {
-					<ItemStatusByItemId<T>>::mutate(_item_id, |x| *x = status);
+			let item = Self::item_by_id(item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item.user_id == who && item.is_accepted(), Error::<T>::NotItemOwner);
+			let previous = Self::review_by_item_id(item_id);
+			ensure!(
+				previous.as_ref().map_or(true, |r| r.decided.is_some()),
+				Error::<T>::ReviewPending
+			);
+
+			let required = match item.certificate_id {
+				Some(cid) => Some(Self::ensure_certificate_is_valid(cid, &who)?.org),
+				None if Self::item_status_by_item_id(item_id) == Status::Deny =>
+					previous.map(|r| r.verifier),
+				None => None,
+			};
+			let verifier = match required {
+				Some(required) => {
+					ensure!(
+						verifier.map_or(true, |verifier| verifier == required),
+						Error::<T>::InvalidVerifier
+					);
+					required
 				},
-				_ => {
-					<ItemStatusByItemId<T>>::insert(_item_id, status);
+				None => verifier.ok_or(Error::<T>::VerifierRequired)?,
+			};
+			ensure!(
+				<pallet_sys_man::Org<T>>::contains_key(&verifier),
+				Error::<T>::NotAnOrganization
+			);
+
+			<ReviewByItemId<T>>::insert(item_id, Review {
+				verifier: verifier.clone(),
+				requested: WhoAndWhen::<T>::new(who),
+				decided: None,
+				reason: None,
+			});
+			<ItemStatusByItemId<T>>::insert(item_id, Status::Pending);
+			Self::deposit_event(Event::ReviewRequested(item_id, verifier));
+			Ok(())
+		}
+
+		/// Allows or denies item `item_id`, as the org asked to review it.
+		#[pallet::weight(10_000)]
+		pub fn set_status_item(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			status: Status,
+			reason: String,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(status != Status::Pending, Error::<T>::InvalidDecision);
+
+			<ReviewByItemId<T>>::try_mutate(item_id, |review| -> DispatchResult {
+				let review = review.as_mut().ok_or(Error::<T>::ReviewNotPending)?;
+				ensure!(review.verifier == who, Error::<T>::NotItemVerifier);
+				ensure!(review.decided.is_none(), Error::<T>::ReviewNotPending);
+				review.decided = Some(WhoAndWhen::<T>::new(who.clone()));
+				review.reason = Some(reason);
+				Ok(())
+			})?;
+			<ItemStatusByItemId<T>>::insert(item_id, status);
+			Self::deposit_event(Event::SetStatusSucceed(item_id));
+			Ok(())
+		}
+
+		/// Lets `viewer` see the items of the caller's CV in `scope` until block `expires`,
+		/// replacing any access it had to that scope. An account gives at most `MaxAccessGrants`
+		/// grants at a time.
+		#[pallet::weight(10_000)]
+		pub fn grant_access(
+			origin: OriginFor<T>,
+			viewer: T::AccountId,
+			scope: AccessScope<T::Hash>,
+			expires: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(expires > <frame_system::Pallet<T>>::block_number(), Error::<T>::GrantExpired);
+			if let AccessScope::Item(item_id) = scope {
+				let item = Self::item_by_id(item_id).ok_or(Error::<T>::ItemNotFound)?;
+				ensure!(item.user_id == who && item.is_accepted(), Error::<T>::NotItemOwner);
+			}
+			if !<AccessGrants<T>>::contains_key(&who, (viewer.clone(), scope)) {
+				<AccessGrantCount<T>>::try_mutate(&who, |count| -> DispatchResult {
+					ensure!(*count < T::MaxAccessGrants::get(), Error::<T>::TooManyAccessGrants);
+					*count += 1;
+					Ok(())
+				})?;
+			}
+
+			<AccessGrants<T>>::insert(&who, (viewer.clone(), scope), AccessGrant {
+				granted: WhoAndWhen::<T>::new(who.clone()),
+				expires,
+			});
+			Self::deposit_event(Event::AccessGranted(who, viewer, scope, expires));
+			Ok(())
+		}
+
+		/// Withdraws the access of `viewer` to the items of the caller's CV in `scope`, effective
+		/// immediately.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(
+				4 * Weight::from(T::MaxItems::get()),
+				Weight::from(T::MaxItems::get()),
+			)
+		)]
+		pub fn revoke_access(
+			origin: OriginFor<T>,
+			viewer: T::AccountId,
+			scope: AccessScope<T::Hash>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<AccessGrants<T>>::take(&who, (viewer.clone(), scope))
+				.ok_or(Error::<T>::GrantNotFound)?;
+			<AccessGrantCount<T>>::mutate(&who, |count| *count = count.saturating_sub(1));
+
+			// Drop the content keys the grant let the viewer read, unless it issued the item.
+			for item in Self::items_by_accountid(&who).into_iter().filter_map(Self::item_by_id) {
+				if <ItemKeys<T>>::contains_key(item.item_id, &viewer) &&
+					*item.issuer() != viewer &&
+					!Self::can_view(viewer.clone(), item.item_id)
+				{
+					<ItemKeys<T>>::remove(item.item_id, &viewer);
 				}
 			}
-			Self::deposit_event(Event::SetStatusSucceed(_item_id));
+			Self::deposit_event(Event::AccessRevoked(who, viewer, scope));
+			Ok(())
+		}
+
+		/// Seals the content key of encrypted item `item_id` to `viewer`, who must have access to
+		/// it. `envelope` replaces any key the viewer had.
+		///
+		/// Counting the readers of the item reads up to `MaxItemKeys` envelopes.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(Weight::from(T::MaxItemKeys::get()), 1)
+		)]
+		pub fn share_item_key(
+			origin: OriginFor<T>,
+			item_id: T::Hash,
+			viewer: T::AccountId,
+			envelope: Envelope,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let item = Self::item_by_id(item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item.user_id == who && item.is_accepted(), Error::<T>::NotItemOwner);
+			ensure!(item.encrypted, Error::<T>::ItemNotEncrypted);
+			ensure!(Self::can_view(viewer.clone(), item_id), Error::<T>::NoAccess);
+			Self::ensure_envelope_is_valid(&viewer, &envelope)?;
+			ensure!(
+				<ItemKeys<T>>::contains_key(item_id, &viewer) ||
+					<ItemKeys<T>>::iter_prefix_values(item_id).count() <
+						T::MaxItemKeys::get() as usize,
+				Error::<T>::TooManyItemKeys
+			);
+
+			<ItemKeys<T>>::insert(item_id, &viewer, envelope);
+			Self::deposit_event(Event::ItemKeyShared(item_id, viewer));
+			Ok(())
+		}
+
+		/// Takes item `item_id`, proposed by someone else, into the caller's CV, refunding the
+		/// deposit of its issuer.
+		#[pallet::weight(10_000)]
+		pub fn accept_item(origin: OriginFor<T>, item_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_cv_has_room(&who)?;
+			Self::take_pending(&who, item_id)?;
+
+			<ItemById<T>>::mutate(item_id, |item| {
+				if let Some(item) = item {
+					item.accepted = Some(WhoAndWhen::<T>::new(who.clone()));
+					if let Some(deposit) = <ProposalDeposits<T>>::take(item_id) {
+						T::Currency::unreserve(item.issuer(), deposit);
+					}
+				}
+			});
+			<ItemsByAccountId<T>>::mutate(&who, |x| x.push(item_id));
+			Self::deposit_event(Event::ItemAccepted(item_id));
+			Ok(())
+		}
+
+		/// Turns down item `item_id`, proposed by someone else, deleting it. The deposit of its
+		/// issuer is slashed.
+		#[pallet::weight(10_000)]
+		pub fn reject_item(origin: OriginFor<T>, item_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::take_pending(&who, item_id)?;
+
+			if let Some(item) = Self::remove_proposal(item_id) {
+				if let Some(deposit) = <ProposalDeposits<T>>::take(item_id) {
+					let _ = T::Currency::slash_reserved(item.issuer(), deposit);
+				}
+			}
+			Self::deposit_event(Event::ItemRejected(item_id));
+			Ok(())
+		}
+
+		/// Takes back item `item_id`, which the caller proposed and its subject has not decided
+		/// on yet, deleting it and refunding the deposit.
+		#[pallet::weight(10_000)]
+		pub fn withdraw_proposal(origin: OriginFor<T>, item_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let item = Self::item_by_id(item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(*item.issuer() == who, Error::<T>::NotItemIssuer);
+			Self::take_pending(&item.user_id, item_id)?;
+
+			Self::remove_proposal(item_id);
+			if let Some(deposit) = <ProposalDeposits<T>>::take(item_id) {
+				T::Currency::unreserve(&who, deposit);
+			}
+			Self::deposit_event(Event::ProposalWithdrawn(item_id));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Checks `fields` take the fields their kind requires, and only those.
+		fn ensure_fields_are_valid(fields: &ItemFields) -> DispatchResult {
+			let max_length = T::MaxFieldLength::get() as usize;
+			let valid_length = |text: &String| !text.is_empty() && text.len() <= max_length;
+			ensure!(valid_length(&fields.title), Error::<T>::InvalidFieldLength);
+			if let Some(institution) = &fields.institution {
+				ensure!(valid_length(institution), Error::<T>::InvalidFieldLength);
+			}
+
+			match fields.kind {
+				ItemKind::Education | ItemKind::Employment | ItemKind::Volunteer => {
+					ensure!(fields.institution.is_some(), Error::<T>::MissingField);
+					ensure!(fields.level.is_none(), Error::<T>::UnexpectedField);
+				},
+				ItemKind::Project => {
+					ensure!(fields.level.is_none(), Error::<T>::UnexpectedField);
+				},
+				ItemKind::Publication | ItemKind::Award => {
+					ensure!(
+						fields.level.is_none() && fields.end.is_none(),
+						Error::<T>::UnexpectedField
+					);
+				},
+				ItemKind::Skill | ItemKind::Language => {
+					ensure!(
+						fields.institution.is_none() &&
+							fields.start.is_none() && fields.end.is_none(),
+						Error::<T>::UnexpectedField
+					);
+					let max_level = if fields.kind == ItemKind::Skill {
+						MAX_SKILL_LEVEL
+					} else {
+						MAX_LANGUAGE_LEVEL
+					};
+					let level = fields.level.ok_or(Error::<T>::MissingField)?;
+					ensure!((1..=max_level).contains(&level), Error::<T>::InvalidLevel);
+				},
+			}
+			if let (Some(start), Some(end)) = (fields.start, fields.end) {
+				ensure!(start < end, Error::<T>::InvalidPeriod);
+			}
 			Ok(())
 		}
+
+		/// Checks the item expires after it was issued, and that no date lies too far ahead.
+		fn ensure_dates_are_valid(
+			fields: &ItemFields,
+			org_date: Option<UnixEpoch>,
+			exp_date: Option<UnixEpoch>,
+		) -> DispatchResult {
+			if let (Some(org_date), Some(exp_date)) = (org_date, exp_date) {
+				ensure!(org_date < exp_date, Error::<T>::InvalidExpiry);
+			}
+			let horizon =
+				<pallet_utils::Pallet<T>>::unix_now().saturating_add(T::MaxFutureDate::get());
+			ensure!(
+				[org_date, exp_date, fields.start, fields.end]
+					.iter()
+					.flatten()
+					.all(|date| *date <= horizon),
+				Error::<T>::DateTooFarInFuture
+			);
+			Ok(())
+		}
+
+		/// Checks the content key of an item of `subject` claimed by `issuer` is only sealed to
+		/// them, including to the subject, and to keys they registered.
+		fn ensure_envelopes_are_valid(
+			subject: &T::AccountId,
+			issuer: &T::AccountId,
+			envelopes: &[(T::AccountId, Envelope)],
+		) -> DispatchResult {
+			if envelopes.is_empty() {
+				return Ok(())
+			}
+			ensure!(envelopes.len() <= T::MaxItemKeys::get() as usize, Error::<T>::TooManyItemKeys);
+			ensure!(
+				envelopes.iter().any(|(reader, _)| reader == subject),
+				Error::<T>::SubjectKeyRequired
+			);
+			for (reader, envelope) in envelopes {
+				ensure!(reader == subject || reader == issuer, Error::<T>::InvalidRecipient);
+				Self::ensure_envelope_is_valid(reader, envelope)?;
+			}
+			Ok(())
+		}
+
+		/// Checks `envelope` is well-formed and `reader` has a key to open it with.
+		fn ensure_envelope_is_valid(reader: &T::AccountId, envelope: &Envelope) -> DispatchResult {
+			ensure!(envelope.len() == ENVELOPE_LENGTH, Error::<T>::InvalidEnvelope);
+			ensure!(
+				<pallet_account::EncryptionKeys<T>>::contains_key(reader),
+				Error::<T>::EncryptionKeyNotRegistered
+			);
+			Ok(())
+		}
+
+		/// Status of item `item_id` at the current block, `None` if there is no such item.
+		pub fn effective_status(item_id: T::Hash) -> Option<EffectiveStatus> {
+			if <TombstoneByItemId<T>>::contains_key(item_id) {
+				return Some(EffectiveStatus::Revoked)
+			}
+			let item = Self::item_by_id(item_id)?;
+			Some(match Self::item_status_by_item_id(item_id) {
+				Status::Deny => EffectiveStatus::Deny,
+				_ if item.is_expired(<pallet_utils::Pallet<T>>::unix_now()) =>
+					EffectiveStatus::Expired,
+				Status::Allow => EffectiveStatus::Allow,
+				Status::Pending => EffectiveStatus::Pending,
+			})
+		}
+
+		/// Score of the CV of `account` at the current block, item by item. Only allowed items
+		/// count: pending, denied and expired ones are left out, as are items backed by a
+		/// certificate an earlier item already counted.
+		pub fn cv_score(account: T::AccountId) -> CvScore<T::Hash> {
+			let now = <pallet_utils::Pallet<T>>::unix_now();
+			let mut counted = BTreeSet::new();
+			let items: Vec<_> = Self::items_by_accountid(account)
+				.into_iter()
+				.filter(|id| Self::effective_status(*id) == Some(EffectiveStatus::Allow))
+				.filter_map(|id| Some((Self::item_by_id(id)?, Self::review_by_item_id(id)?)))
+				.filter(|(item, _)| item.certificate_id.map_or(true, |cid| counted.insert(cid)))
+				.map(|(item, review)| {
+					let context = ItemScoreContext {
+						kind: item.fields.as_ref().map(|fields| fields.kind),
+						certificate_score: item
+							.certificate_id
+							.and_then(<pallet_certificate::Pallet<T>>::certificate_by_id)
+							.map(|certificate| certificate.score),
+						reputation: <pallet_sys_man::Pallet<T>>::org_profile(&review.verifier)
+							.reputation,
+						age: now.saturating_sub(item.last_active(now)),
+					};
+					let score = T::CvScoringPolicy::score(&context);
+					ItemScore { item_id: item.item_id, context, score }
+				})
+				.collect();
+
+			let total = items.iter().fold(0u32, |total, item| total.saturating_add(item.score));
+			CvScore { total, items }
+		}
+
+		/// Whether `viewer` may see item `item_id` at the current block: it is the owner, or was
+		/// granted access to the item or its kind and the grant has not expired.
+		pub fn can_view(viewer: T::AccountId, item_id: T::Hash) -> bool {
+			let item = match Self::item_by_id(item_id) {
+				Some(item) if item.is_accepted() => item,
+				_ => return false,
+			};
+			if item.user_id == viewer {
+				return true
+			}
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let granted = |scope| {
+				Self::access_grant(&item.user_id, (viewer.clone(), scope))
+					.map_or(false, |grant| now < grant.expires)
+			};
+			granted(AccessScope::Item(item_id)) ||
+				item.fields.as_ref().map_or(false, |fields| granted(AccessScope::Kind(fields.kind)))
+		}
+
+		/// Items in the CV of `account` whose fields match `filter`.
+		pub fn filter_items(account: T::AccountId, filter: ItemFilter) -> Vec<Item<T>> {
+			Self::items_by_accountid(account)
+				.into_iter()
+				.filter_map(Self::item_by_id)
+				.filter(|item| item.fields.as_ref().map_or(false, |fields| filter.matches(fields)))
+				.collect()
+		}
+
+		/// Certificate `cid`, provided it is currently valid for `subject`.
+		fn ensure_certificate_is_valid(
+			cid: T::Hash,
+			subject: &T::AccountId,
+		) -> Result<Certificate<T>, DispatchError> {
+			let certificate = <pallet_certificate::Pallet<T>>::certificate_by_id(cid)
+				.ok_or(Error::<T>::CertificateNotFound)?;
+			match <pallet_certificate::Pallet<T>>::verify(cid, subject.clone()) {
+				Verdict::Valid => Ok(certificate),
+				Verdict::Unknown => Err(Error::<T>::NotCertificateHolder.into()),
+				Verdict::Revoked(_) | Verdict::IssuerRevoked =>
+					Err(Error::<T>::CertificateRevoked.into()),
+				Verdict::Expired => Err(Error::<T>::CertificateExpired.into()),
+				Verdict::Suspended | Verdict::Pending =>
+					Err(Error::<T>::CertificateNotActive.into()),
+			}
+		}
+
+		/// Whether `fields` describe what `certificate` attests: an award for award classes,
+		/// education otherwise, titled after the class.
+		fn matches_certificate(fields: &ItemFields, certificate: &Certificate<T>) -> bool {
+			let class = match <pallet_certificate::Pallet<T>>::class_by_id(certificate.class_id) {
+				Some(class) if class.org == certificate.org => class,
+				_ => return false,
+			};
+			let kind = match class.kind {
+				ClassKind::Award => ItemKind::Award,
+				_ => ItemKind::Education,
+			};
+			fields.kind == kind && fields.title == class.name
+		}
+
+		/// Forgets that `item` is backed by its certificate.
+		fn unlink_certificate(item: &Item<T>) {
+			if let Some(cid) = item.certificate_id {
+				<ItemsByCertificate<T>>::mutate_exists(cid, |items| {
+					if let Some(ids) = items {
+						ids.retain(|id| *id != item.item_id);
+						if ids.is_empty() {
+							*items = None;
+						}
+					}
+				});
+			}
+		}
+
+		/// Deletes item `item_id`, which was taken out of the inbox of its subject.
+		fn remove_proposal(item_id: T::Hash) -> Option<Item<T>> {
+			let item = <ItemById<T>>::take(item_id);
+			if let Some(item) = &item {
+				<ItemIdByNumber<T>>::remove(item.number);
+				Self::unlink_certificate(item);
+			}
+			<ItemStatusByItemId<T>>::remove(item_id);
+			<ReviewByItemId<T>>::remove(item_id);
+			<ItemVersions<T>>::remove(item_id);
+			let _ = <ItemKeys<T>>::remove_prefix(item_id, None);
+			item
+		}
+
+		/// Checks the CV of `account` can take one more item.
+		fn ensure_cv_has_room(account: &T::AccountId) -> DispatchResult {
+			ensure!(
+				<ItemsByAccountId<T>>::decode_len(account).unwrap_or(0) <
+					T::MaxItems::get() as usize,
+				Error::<T>::TooManyItems
+			);
+			Ok(())
+		}
+
+		/// Removes `item_id` from the inbox of `subject`.
+		fn take_pending(subject: &T::AccountId, item_id: T::Hash) -> DispatchResult {
+			<PendingItemsByAccountId<T>>::try_mutate(subject, |x| {
+				let index =
+					x.iter().position(|id| *id == item_id).ok_or(Error::<T>::ItemNotPending)?;
+				x.remove(index);
+				Ok(())
+			})
+		}
+	}
+
+	impl<T: Config> OnCertificateRevoked<T> for Pallet<T> {
+		/// Denies the items backed by `certificate`, recording its revocation as the decision.
+		fn on_certificate_revoked(certificate: &Certificate<T>) {
+			let revocation = match certificate.revocations().next() {
+				Some(revocation) => revocation.clone(),
+				None => return,
+			};
+			for item_id in Self::items_by_certificate(certificate.cid) {
+				<ReviewByItemId<T>>::mutate(item_id, |review| {
+					*review = Some(Review {
+						verifier: certificate.org.clone(),
+						requested: review
+							.take()
+							.map_or_else(|| revocation.revoked.clone(), |r| r.requested),
+						decided: Some(revocation.revoked.clone()),
+						reason: Some(revocation.reason.clone()),
+					});
+				});
+				<ItemStatusByItemId<T>>::insert(item_id, Status::Deny);
+				Self::deposit_event(Event::SetStatusSucceed(item_id));
+			}
+		}
 	}
-}
\ No newline at end of file
+}
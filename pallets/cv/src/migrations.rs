@@ -0,0 +1,169 @@
+//! Storage migrations of the CV pallet.
+
+pub mod v1 {
+	//! Moves items from sequential `u32` ids to hash ids derived with
+	//! [`pallet_utils::Pallet::derive_id`], and links them to certificates by hash id.
+	//!
+	//! Items used to be listed under the account that created them. Items an account created
+	//! about itself stay in its CV; those about someone else go to the subject's inbox, awaiting
+	//! its consent like any other proposal.
+	//!
+	//! Owners could set the status of their own items before reviews, so legacy statuses are
+	//! dropped: every migrated item is pending until an org reviews it.
+	//!
+	//! Must run after the matching certificate migration, which maps legacy certificate numbers.
+
+	use crate::*;
+	use codec::{Decode, Input};
+	use core::marker::PhantomData;
+	use frame_support::{
+		inherent::Vec,
+		log,
+		storage::migration::{storage_key_iter, take_storage_item},
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+		weights::Weight,
+		Twox64Concat,
+	};
+	use pallet_utils::{Content, String, TypeID, UnixEpoch, WhoAndWhen};
+
+	/// SCALE encoding of an item stored before v1, read as is so entries that do not decode can
+	/// be told apart and left in place.
+	struct RawItem(Vec<u8>);
+
+	impl Decode for RawItem {
+		fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+			let len = input.remaining_len()?.ok_or("unknown item length")?;
+			let mut bytes = Vec::new();
+			bytes.resize(len, 0);
+			input.read(&mut bytes)?;
+			Ok(Self(bytes))
+		}
+	}
+
+	/// An item stored before v1.
+	#[derive(Decode)]
+	struct LegacyItem<T: Config> {
+		item_id: TypeID,
+		user_id: T::AccountId,
+		created: WhoAndWhen<T>,
+		org_date: Option<UnixEpoch>,
+		exp_date: Option<UnixEpoch>,
+		certificate_id: Option<TypeID>,
+		score: u32,
+		metadata: String,
+	}
+
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return 0
+			}
+
+			let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			let items: Vec<(TypeID, RawItem)> =
+				storage_key_iter::<TypeID, RawItem, Twox64Concat>(pallet, b"ItemById").collect();
+			let statuses: Vec<TypeID> =
+				storage_key_iter::<TypeID, Status, Twox64Concat>(pallet, b"ItemStatusByItemId")
+					.map(|(number, _)| number)
+					.collect();
+			let mut reads = (items.len() + statuses.len()) as Weight;
+			let mut writes = 0;
+
+			for (key, RawItem(raw)) in items {
+				let old = match LegacyItem::<T>::decode(&mut &raw[..]) {
+					Ok(old) => old,
+					Err(_) => {
+						log::warn!(
+							target: "runtime::cv",
+							"item {} does not decode, left under its legacy id",
+							key,
+						);
+						continue
+					},
+				};
+				let number = old.item_id;
+				let item_id =
+					<pallet_utils::Pallet<T>>::derive_id(b"item", &old.created.account, number);
+				let certificate_id = old
+					.certificate_id
+					.and_then(pallet_certificate::Pallet::<T>::certificate_id_by_number);
+				let accepted = if old.user_id == old.created.account {
+					Some(old.created.clone())
+				} else {
+					None
+				};
+				if accepted.is_none() {
+					<PendingItemsByAccountId<T>>::append(&old.user_id, item_id);
+					writes += 1;
+				}
+				let item = Item {
+					item_id,
+					number,
+					user_id: old.user_id,
+					accepted,
+					fields: None,
+					created: old.created,
+					org_date: old.org_date,
+					exp_date: old.exp_date,
+					certificate_id,
+					score: old.score,
+					metadata: old.metadata,
+					encrypted: false,
+					content: Content::None,
+					content_hash: None,
+					version: 0,
+				};
+				let _ =
+					take_storage_item::<TypeID, RawItem, Twox64Concat>(pallet, b"ItemById", key);
+				<ItemVersions<T>>::append(
+					item_id,
+					ItemVersion::of(&item, item.created.clone(), Vec::new()),
+				);
+				<ItemById<T>>::insert(item_id, item);
+				<ItemIdByNumber<T>>::insert(number, item_id);
+				if let Some(cid) = certificate_id {
+					<ItemsByCertificate<T>>::append(cid, item_id);
+					writes += 1;
+				}
+				reads += 1;
+				writes += 4;
+			}
+
+			for number in statuses {
+				if Pallet::<T>::item_id_by_number(number).is_some() {
+					let _ = take_storage_item::<TypeID, Status, Twox64Concat>(
+						pallet,
+						b"ItemStatusByItemId",
+						number,
+					);
+					writes += 1;
+				}
+				reads += 1;
+			}
+
+			// Lists were kept by creator, keep only the items each account created about itself.
+			<ItemsByAccountId<T>>::translate::<Vec<TypeID>, _>(|account, numbers| {
+				reads += 2 * numbers.len() as Weight + 1;
+				writes += 1;
+				let ids: Vec<T::Hash> = numbers
+					.into_iter()
+					.filter_map(Pallet::<T>::item_id_by_number)
+					.filter(|id| {
+						Pallet::<T>::item_by_id(id)
+							.map_or(false, |item| item.user_id == account && item.is_accepted())
+					})
+					.collect();
+				if ids.is_empty() {
+					None
+				} else {
+					Some(ids)
+				}
+			});
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes + 1)
+		}
+	}
+}
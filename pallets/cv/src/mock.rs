@@ -1,4 +1,6 @@
-use crate as pallet_template;
+//! mock setup for testing pallet-cv functionalities
+
+use crate as pallet_cv;
 use frame_support::parameter_types;
 use frame_system as system;
 use sp_core::H256;
@@ -18,7 +20,14 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
+		Utils: pallet_utils::{Pallet, Storage, Event<T>},
+		AccountModule: pallet_account::{Pallet, Call, Storage, Event<T>},
+		SysMan: pallet_sys_man::{Pallet, Call, Storage, Event<T>},
+		CertificateModule: pallet_certificate::{Pallet, Call, Storage, Event<T>},
+		CvModule: pallet_cv::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -45,7 +54,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -53,11 +62,128 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
-impl pallet_template::Config for Test {
+parameter_types! {
+	pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
 	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const ClassDeposit: u64 = 0;
+	pub const InstanceDeposit: u64 = 0;
+	pub const MetadataDepositBase: u64 = 0;
+	pub const MetadataDepositPerByte: u64 = 0;
+	pub const StringLimit: u32 = 50;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 64;
+}
+
+impl pallet_uniques::Config for Test {
+	type Event = Event;
+	type ClassId = u32;
+	type InstanceId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type ClassDeposit = ClassDeposit;
+	type InstanceDeposit = InstanceDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type AttributeDepositBase = MetadataDepositBase;
+	type DepositPerByte = MetadataDepositPerByte;
+	type StringLimit = StringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = ();
+}
+
+impl pallet_utils::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+}
+
+impl pallet_sys_man::Config for Test {
+	type Event = Event;
+}
+
+parameter_types! {
+	pub const CertificateDepositBase: u64 = 10;
+	pub const CertificateDepositPerByte: u64 = 1;
+	pub const RequestTimeout: u64 = 100;
+	pub const RequestDeposit: u64 = 5;
+	pub const MaxPendingRequests: u32 = 2;
+}
+
+impl pallet_certificate::Config for Test {
+	type Event = Event;
+	type ScoringPolicy = pallet_certificate::scoring::DefaultScoringPolicy;
+	type Nfts = Uniques;
+	type CertificateDepositBase = CertificateDepositBase;
+	type CertificateDepositPerByte = CertificateDepositPerByte;
+	type RequestTimeout = RequestTimeout;
+	type RequestDeposit = RequestDeposit;
+	type MaxPendingRequests = MaxPendingRequests;
+	type OnRevoke = CvModule;
+}
+
+impl pallet_account::Config for Test {
+	type Event = Event;
+}
+
+parameter_types! {
+	pub const MaxItems: u32 = 4;
+	pub const MaxPendingItems: u32 = 2;
+	pub const ProposalDeposit: u64 = 5;
+	pub const MaxFieldLength: u32 = 32;
+	pub const MaxFutureDate: u64 = 1_000;
+	pub const MaxItemKeys: u32 = 4;
+	pub const MaxAccessGrants: u32 = 3;
+}
+
+impl pallet_cv::Config for Test {
+	type Event = Event;
+	type MaxItems = MaxItems;
+	type MaxPendingItems = MaxPendingItems;
+	type ProposalDeposit = ProposalDeposit;
+	type MaxFieldLength = MaxFieldLength;
+	type MaxFutureDate = MaxFutureDate;
+	type MaxItemKeys = MaxItemKeys;
+	type MaxAccessGrants = MaxAccessGrants;
+	type CvScoringPolicy = pallet_cv::scoring::DefaultScoringPolicy;
+}
+
+/// Unix time the tests start at, in seconds.
+pub const NOW: u64 = 1_650_000_000;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: (1..=4).map(|who| (who, 1_000)).collect() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		Timestamp::set_timestamp(NOW * 1000);
+	});
+	ext
 }
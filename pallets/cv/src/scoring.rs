@@ -0,0 +1,75 @@
+//! Scoring of CVs.
+//!
+//! A CV scores the sum of its verified items. What an item is worth is decided by a policy
+//! plugged into the pallet's `Config`, and the per-item breakdown is exposed so job platforms
+//! can explain how they rank candidates.
+
+use crate::ItemKind;
+use codec::{Decode, Encode};
+use frame_support::inherent::Vec;
+use pallet_utils::UnixEpoch;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Everything a [`ScoringPolicy`] may take into account about a verified item.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ItemScoreContext {
+	/// Kind of the item, `None` for items created before items were typed.
+	pub kind: Option<ItemKind>,
+	/// Score of the certificate backing the item, if any.
+	pub certificate_score: Option<u32>,
+	/// Reputation of the org that verified the item, out of `pallet_sys_man::MAX_REPUTATION`.
+	pub reputation: u8,
+	/// Seconds since the item was last current, zero for ongoing items.
+	pub age: UnixEpoch,
+}
+
+/// Score of a verified item, with what it was computed from.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ItemScore<Hash> {
+	pub item_id: Hash,
+	pub context: ItemScoreContext,
+	pub score: u32,
+}
+
+/// Score of a CV: the sum of the scores of its verified items.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CvScore<Hash> {
+	pub total: u32,
+	pub items: Vec<ItemScore<Hash>>,
+}
+
+/// Decides what a verified CV item is worth.
+pub trait ScoringPolicy {
+	fn score(context: &ItemScoreContext) -> u32;
+}
+
+const YEAR: UnixEpoch = 365 * 24 * 60 * 60;
+
+/// Scores items out of 100.
+///
+/// The certificate score, or 50 for items without a certificate, is weighted by the item kind,
+/// the verifier's reputation (50% to 100%) and recency: full weight for five years, decaying
+/// to 50% at twenty.
+pub struct DefaultScoringPolicy;
+
+impl ScoringPolicy for DefaultScoringPolicy {
+	fn score(context: &ItemScoreContext) -> u32 {
+		let base = u64::from(context.certificate_score.unwrap_or(50).min(100));
+		let kind: u64 = match context.kind {
+			Some(ItemKind::Education) | Some(ItemKind::Employment) => 100,
+			Some(ItemKind::Publication) => 90,
+			Some(ItemKind::Award) => 80,
+			Some(ItemKind::Project) => 70,
+			Some(ItemKind::Volunteer) => 60,
+			Some(ItemKind::Skill) | Some(ItemKind::Language) | None => 50,
+		};
+		let max_reputation = u64::from(pallet_sys_man::MAX_REPUTATION);
+		let reputation =
+			50 + 50 * u64::from(context.reputation).min(max_reputation) / max_reputation;
+		let stale = context.age.saturating_sub(5 * YEAR).min(15 * YEAR);
+		let recency = 100 - 50 * stale / (15 * YEAR);
+
+		(base * kind * reputation * recency / 1_000_000) as u32
+	}
+}
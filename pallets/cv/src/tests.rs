@@ -1,20 +1,965 @@
-use crate::{mock::*, Error};
+//! pallet-cv functionalities test
+use super::*;
+use crate::{mock::*, scoring::ItemScoreContext, Error};
 use frame_support::{assert_noop, assert_ok};
+use pallet_certificate::ClassKind;
+use pallet_utils::{Content, OrgCategory, Role};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+fn str2vec(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
+
+fn register_org(id: u64) {
+	pallet_sys_man::Org::<Test>::insert(
+		id,
+		pallet_sys_man::SysManAccount::<Test> {
+			role: Role::Organization,
+			status: pallet_utils::Status::Active,
+			level: None,
+			parent: None,
+			children: None,
+			metadata: vec![],
+		},
+	);
+}
+
+/// Has org `org` issue a certificate of a fresh class to `holder`.
+fn issue_certificate(org: u64, holder: u64) -> H256 {
+	register_org(org);
+	let class_id = CertificateModule::class_id();
+	assert_ok!(CertificateModule::create_class(
+		Origin::signed(org),
+		ClassKind::Degree,
+		str2vec("BSc in Computer Science"),
+		0,
+		100
+	));
+	let number = CertificateModule::certificate_id();
+	assert_ok!(CertificateModule::create_certificate(
+		Origin::signed(org),
+		holder,
+		class_id,
+		None,
+		vec![],
+		None,
+		Content::None,
+		None,
+		None
+	));
+	CertificateModule::certificate_id_by_number(number).unwrap()
+}
+
+fn degree() -> ItemFields {
+	ItemFields {
+		kind: ItemKind::Education,
+		institution: Some(str2vec("HUST")),
+		title: str2vec("BSc in Computer Science"),
+		start: Some(1_500_000_000),
+		end: Some(1_600_000_000),
+		level: None,
+	}
+}
+
+fn create_item(who: u64) -> H256 {
+	propose_item(who, who)
+}
+
+fn propose_item(issuer: u64, subject: u64) -> H256 {
+	let number = CvModule::item_id();
+	assert_ok!(CvModule::create_item(
+		Origin::signed(issuer),
+		subject,
+		degree(),
+		str2vec("BSc in Computer Science"),
+		None,
+		None,
+		None,
+		Content::None,
+		None,
+		vec![]
+	));
+	CvModule::item_id_by_number(number).unwrap()
+}
+
+#[test]
+fn create_item_should_work() {
+	new_test_ext().execute_with(|| {
+		let first = create_item(1);
+		let second = create_item(1);
+
+		assert_ne!(first, second);
+		assert_eq!(first, Utils::derive_id(b"item", &1, 0));
+		assert_eq!(CvModule::item_id(), 2);
+		assert_eq!(CvModule::items_by_accountid(1), vec![first, second]);
+
+		let item = CvModule::item_by_id(second).unwrap();
+		assert_eq!(item.item_id, second);
+		assert_eq!(item.number, 1);
+		assert_eq!(item.metadata, str2vec("BSc in Computer Science"));
+		assert!(item.is_accepted());
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn item_about_someone_else_should_wait_for_consent() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		let item_id = propose_item(1, 2);
+
+		let item = CvModule::item_by_id(item_id).unwrap();
+		assert_eq!(item.user_id, 2);
+		assert_eq!(*item.issuer(), 1);
+		assert!(!item.is_accepted());
+		assert!(CvModule::items_by_accountid(1).is_empty());
+		assert!(CvModule::items_by_accountid(2).is_empty());
+		assert_eq!(CvModule::pending_items_by_accountid(2), vec![item_id]);
+		assert_eq!(Balances::reserved_balance(1), 5);
+
+		assert_noop!(
+			CvModule::accept_item(Origin::signed(1), item_id),
+			Error::<Test>::ItemNotPending
+		);
+		assert_ok!(CvModule::accept_item(Origin::signed(2), item_id));
+		assert!(CvModule::item_by_id(item_id).unwrap().is_accepted());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+		assert_eq!(CvModule::items_by_accountid(2), vec![item_id]);
+		assert!(CvModule::pending_items_by_accountid(2).is_empty());
+		assert_noop!(
+			CvModule::accept_item(Origin::signed(2), item_id),
+			Error::<Test>::ItemNotPending
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn reject_item_should_delete_it() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		let item_id = propose_item(1, 2);
+
+		assert_ok!(CvModule::reject_item(Origin::signed(2), item_id));
+		assert_eq!(CvModule::item_by_id(item_id), None);
+		assert_eq!(CvModule::item_id_by_number(0), None);
+		assert!(CvModule::pending_items_by_accountid(2).is_empty());
+		assert!(CvModule::items_by_accountid(2).is_empty());
+		// Unwanted proposals cost their issuer the deposit.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 995);
+	});
+}
+
+#[test]
+fn withdraw_proposal_should_refund_issuer() {
+	new_test_ext().execute_with(|| {
+		let item_id = propose_item(1, 2);
+
+		assert_noop!(
+			CvModule::withdraw_proposal(Origin::signed(2), item_id),
+			Error::<Test>::NotItemIssuer
+		);
+		assert_ok!(CvModule::withdraw_proposal(Origin::signed(1), item_id));
+		assert_eq!(CvModule::item_by_id(item_id), None);
+		assert!(CvModule::pending_items_by_accountid(2).is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+
+		// Accepted items are the subject's to revoke.
+		let item_id = propose_item(1, 2);
+		assert_ok!(CvModule::accept_item(Origin::signed(2), item_id));
+		assert_noop!(
+			CvModule::withdraw_proposal(Origin::signed(1), item_id),
+			Error::<Test>::ItemNotPending
+		);
+	});
+}
+
+#[test]
+fn inbox_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		propose_item(1, 2);
+		propose_item(3, 2);
+
+		assert_noop!(
+			CvModule::create_item(
+				Origin::signed(1),
+				2,
+				degree(),
+				str2vec("BSc in Computer Science"),
+				None,
+				None,
+				None,
+				Content::None,
+				None,
+				vec![]
+			),
+			Error::<Test>::TooManyPendingItems
+		);
+		// Self-declared items do not go through the inbox.
+		create_item(2);
+	});
+}
+
+#[test]
+fn revoke_item_should_work() {
+	new_test_ext().execute_with(|| {
+		let first = create_item(1);
+		let item_id = create_item(1);
+		let last = create_item(1);
+		register_org(3);
+		assert_ok!(CvModule::request_review(Origin::signed(1), item_id, Some(3)));
+		assert_ok!(CvModule::set_status_item(Origin::signed(3), item_id, Status::Allow, vec![]));
+
+		assert_noop!(
+			CvModule::revoke_item(Origin::signed(2), item_id, vec![]),
+			Error::<Test>::ItemNotFound
+		);
+
+		assert_ok!(CvModule::revoke_item(Origin::signed(1), item_id, str2vec("Typo")));
+		assert_eq!(CvModule::item_by_id(item_id), None);
+		assert_eq!(CvModule::items_by_accountid(1), vec![first, last]);
+		assert_eq!(CvModule::item_id_by_number(1), None);
+		assert!(!ItemStatusByItemId::<Test>::contains_key(item_id));
+		assert_eq!(CvModule::review_by_item_id(item_id), None);
+
+		// Those who saw the item can tell it was withdrawn, and what it was.
+		let tombstone = CvModule::tombstone_by_item_id(item_id).unwrap();
+		assert_eq!(tombstone.user_id, 1);
+		assert_eq!(tombstone.status, Status::Allow);
+		assert_eq!(tombstone.revoked.account, 1);
+		assert_eq!(tombstone.reason, str2vec("Typo"));
+		assert_eq!(CvModule::effective_status(item_id), Some(EffectiveStatus::Revoked));
+		assert_eq!(CvModule::item_versions(item_id).len(), 1);
+
+		assert_noop!(
+			CvModule::revoke_item(Origin::signed(1), item_id, vec![]),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
+
+#[test]
+fn set_status_item_should_need_review_request() {
+	new_test_ext().execute_with(|| {
+		register_org(3);
+		let item_id = create_item(1);
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Pending);
+
+		// Owners cannot vouch for their own items.
+		assert_noop!(
+			CvModule::set_status_item(Origin::signed(1), item_id, Status::Allow, vec![]),
+			Error::<Test>::ReviewNotPending
+		);
+		assert_noop!(
+			CvModule::request_review(Origin::signed(1), item_id, None),
+			Error::<Test>::VerifierRequired
+		);
+		assert_noop!(
+			CvModule::request_review(Origin::signed(1), item_id, Some(2)),
+			Error::<Test>::NotAnOrganization
+		);
+		assert_noop!(
+			CvModule::request_review(Origin::signed(2), item_id, Some(3)),
+			Error::<Test>::NotItemOwner
+		);
+
+		assert_ok!(CvModule::request_review(Origin::signed(1), item_id, Some(3)));
+		assert_noop!(
+			CvModule::request_review(Origin::signed(1), item_id, Some(3)),
+			Error::<Test>::ReviewPending
+		);
+		assert_noop!(
+			CvModule::set_status_item(Origin::signed(1), item_id, Status::Allow, vec![]),
+			Error::<Test>::NotItemVerifier
+		);
+		assert_noop!(
+			CvModule::set_status_item(Origin::signed(3), item_id, Status::Pending, vec![]),
+			Error::<Test>::InvalidDecision
+		);
+
+		assert_ok!(CvModule::set_status_item(
+			Origin::signed(3),
+			item_id,
+			Status::Deny,
+			str2vec("Not enrolled")
+		));
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Deny);
+		let review = CvModule::review_by_item_id(item_id).unwrap();
+		assert_eq!(review.verifier, 3);
+		assert_eq!(review.decided.map(|d| d.account), Some(3));
+		assert_eq!(review.reason, Some(str2vec("Not enrolled")));
+
+		// A denied item can go back for re-review, but only to the org that denied it.
+		register_org(4);
+		assert_noop!(
+			CvModule::request_review(Origin::signed(1), item_id, Some(4)),
+			Error::<Test>::InvalidVerifier
+		);
+		assert_ok!(CvModule::request_review(Origin::signed(1), item_id, None));
+		assert_eq!(CvModule::review_by_item_id(item_id).unwrap().verifier, 3);
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Pending);
+		assert_ok!(CvModule::set_status_item(
+			Origin::signed(3),
+			item_id,
+			Status::Allow,
+			str2vec("Transcript checked")
+		));
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Allow);
+	});
+}
+
+#[test]
+fn certificate_issuer_should_review_linked_item() {
+	new_test_ext().execute_with(|| {
+		let cid = issue_certificate(3, 1);
+		register_org(4);
+		let number = CvModule::item_id();
+		assert_ok!(CvModule::create_item(
+			Origin::signed(1),
+			1,
+			degree(),
+			str2vec("BSc in Computer Science"),
+			None,
+			None,
+			Some(cid),
+			Content::None,
+			None,
+			vec![]
+		));
+		let item_id = CvModule::item_id_by_number(number).unwrap();
+
+		assert_noop!(
+			CvModule::request_review(Origin::signed(1), item_id, Some(4)),
+			Error::<Test>::InvalidVerifier
+		);
+		assert_ok!(CvModule::request_review(Origin::signed(1), item_id, None));
+		assert_eq!(CvModule::review_by_item_id(item_id).unwrap().verifier, 3);
+	});
+}
+
+#[test]
+fn item_backed_by_certificate_should_be_verified() {
+	new_test_ext().execute_with(|| {
+		let cid = issue_certificate(3, 1);
+		let create = |who: u64, subject: u64| {
+			CvModule::create_item(
+				Origin::signed(who),
+				subject,
+				degree(),
+				str2vec("BSc in Computer Science"),
+				None,
+				None,
+				Some(cid),
+				Content::None,
+				None,
+				vec![],
+			)
+		};
+
+		assert_noop!(create(2, 2), Error::<Test>::NotCertificateHolder);
+		assert_noop!(
+			CvModule::create_item(
+				Origin::signed(1),
+				1,
+				degree(),
+				vec![],
+				None,
+				None,
+				Some(H256::repeat_byte(7)),
+				Content::None,
+				None,
+				vec![]
+			),
+			Error::<Test>::CertificateNotFound
+		);
+
+		assert_ok!(create(1, 1));
+		let item_id = CvModule::item_id_by_number(0).unwrap();
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Allow);
+		assert_eq!(CvModule::review_by_item_id(item_id).unwrap().verifier, 3);
+		assert_eq!(CvModule::items_by_certificate(cid), vec![item_id]);
+		assert_noop!(create(1, 1), Error::<Test>::CertificateAlreadyLinked);
+
+		// Revoking the certificate denies the item.
+		assert_ok!(CertificateModule::revoke_certificate(
+			Origin::signed(3),
+			cid,
+			str2vec("Plagiarism")
+		));
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Deny);
+		let review = CvModule::review_by_item_id(item_id).unwrap();
+		assert_eq!(review.decided.map(|d| d.account), Some(3));
+		assert_eq!(review.reason, Some(str2vec("Plagiarism")));
+
+		// The denial stands: a revoked certificate cannot be reviewed again.
+		assert_noop!(
+			CvModule::request_review(Origin::signed(1), item_id, None),
+			Error::<Test>::CertificateRevoked
+		);
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Deny);
+
+		assert_noop!(create(1, 1), Error::<Test>::CertificateRevoked);
+	});
+}
+
+#[test]
+fn item_not_matching_certificate_should_await_issuer() {
+	new_test_ext().execute_with(|| {
+		let cid = issue_certificate(3, 1);
+		let number = CvModule::item_id();
+		assert_ok!(CvModule::create_item(
+			Origin::signed(1),
+			1,
+			ItemFields { title: str2vec("PhD in Physics"), ..degree() },
+			vec![],
+			None,
+			None,
+			Some(cid),
+			Content::None,
+			None,
+			vec![]
+		));
+		let item_id = CvModule::item_id_by_number(number).unwrap();
+
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Pending);
+		let review = CvModule::review_by_item_id(item_id).unwrap();
+		assert_eq!(review.verifier, 3);
+		assert_eq!(review.decided, None);
+		assert_ok!(CvModule::set_status_item(
+			Origin::signed(3),
+			item_id,
+			Status::Allow,
+			str2vec("Doctorate confirmed")
+		));
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Allow);
+	});
+}
+
+#[test]
+fn item_fields_should_be_validated() {
+	new_test_ext().execute_with(|| {
+		let create = |fields: ItemFields| {
+			CvModule::create_item(
+				Origin::signed(1),
+				1,
+				fields,
+				vec![],
+				None,
+				None,
+				None,
+				Content::None,
+				None,
+				vec![],
+			)
+		};
+		let skill = ItemFields {
+			kind: ItemKind::Skill,
+			institution: None,
+			title: str2vec("Rust"),
+			start: None,
+			end: None,
+			level: Some(4),
+		};
+
+		assert_noop!(
+			create(ItemFields { title: vec![], ..degree() }),
+			Error::<Test>::InvalidFieldLength
+		);
+		assert_noop!(
+			create(ItemFields { title: vec![b'a'; 33], ..degree() }),
+			Error::<Test>::InvalidFieldLength
+		);
+		assert_noop!(
+			create(ItemFields { institution: None, ..degree() }),
+			Error::<Test>::MissingField
+		);
+		assert_noop!(
+			create(ItemFields { start: Some(1_600_000_000), ..degree() }),
+			Error::<Test>::InvalidPeriod
+		);
+		assert_noop!(
+			create(ItemFields { level: None, ..skill.clone() }),
+			Error::<Test>::MissingField
+		);
+		assert_noop!(
+			create(ItemFields { level: Some(6), ..skill.clone() }),
+			Error::<Test>::InvalidLevel
+		);
+		assert_noop!(
+			create(ItemFields { start: Some(1), ..skill.clone() }),
+			Error::<Test>::UnexpectedField
+		);
+		assert_ok!(create(ItemFields {
+			kind: ItemKind::Language,
+			level: Some(6),
+			..skill.clone()
+		}));
+		assert_ok!(create(skill));
+	});
+}
+
+#[test]
+fn filter_items_should_match_fields() {
+	new_test_ext().execute_with(|| {
+		let education = create_item(1);
+		assert_ok!(CvModule::create_item(
+			Origin::signed(1),
+			1,
+			ItemFields {
+				kind: ItemKind::Skill,
+				institution: None,
+				title: str2vec("Rust"),
+				start: None,
+				end: None,
+				level: Some(4),
+			},
+			vec![],
+			None,
+			None,
+			None,
+			Content::None,
+			None,
+			vec![]
+		));
+		let skill = CvModule::item_id_by_number(1).unwrap();
+		let ids = |filter: ItemFilter| -> Vec<H256> {
+			CvModule::filter_items(1, filter).into_iter().map(|item| item.item_id).collect()
+		};
+
+		assert_eq!(ids(ItemFilter::default()), vec![education, skill]);
+		assert_eq!(
+			ids(ItemFilter { kind: Some(ItemKind::Skill), ..Default::default() }),
+			vec![skill]
+		);
+		assert_eq!(
+			ids(ItemFilter { institution: Some(str2vec("HUST")), ..Default::default() }),
+			vec![education]
+		);
+		assert_eq!(
+			ids(ItemFilter { title: Some(str2vec("Computer")), ..Default::default() }),
+			vec![education]
+		);
+		assert_eq!(ids(ItemFilter { min_level: Some(5), ..Default::default() }), vec![]);
+		assert_eq!(
+			ids(ItemFilter { active_at: Some(1_700_000_000), ..Default::default() }),
+			vec![skill]
+		);
+		// Items awaiting consent are not part of the CV.
+		propose_item(2, 1);
+		assert_eq!(ids(ItemFilter::default()), vec![education, skill]);
+	});
+}
+
+#[test]
+fn item_dates_should_be_validated() {
+	new_test_ext().execute_with(|| {
+		let create = |org_date: Option<u64>, exp_date: Option<u64>| {
+			CvModule::create_item(
+				Origin::signed(1),
+				1,
+				degree(),
+				vec![],
+				org_date,
+				exp_date,
+				None,
+				Content::None,
+				None,
+				vec![],
+			)
+		};
+
+		assert_noop!(create(Some(NOW), Some(NOW)), Error::<Test>::InvalidExpiry);
+		assert_noop!(create(Some(NOW), Some(NOW - 1)), Error::<Test>::InvalidExpiry);
+		assert_noop!(create(None, Some(NOW + 1_001)), Error::<Test>::DateTooFarInFuture);
+		assert_noop!(
+			CvModule::create_item(
+				Origin::signed(1),
+				1,
+				ItemFields { end: Some(NOW + 1_001), ..degree() },
+				vec![],
+				None,
+				None,
+				None,
+				Content::None,
+				None,
+				vec![]
+			),
+			Error::<Test>::DateTooFarInFuture
+		);
+		assert_ok!(create(Some(NOW - 100), Some(NOW + 1_000)));
+	});
+}
+
+#[test]
+fn expired_item_should_report_expired() {
+	new_test_ext().execute_with(|| {
+		register_org(3);
+		assert_ok!(CvModule::create_item(
+			Origin::signed(1),
+			1,
+			degree(),
+			vec![],
+			Some(NOW - 100),
+			Some(NOW + 10),
+			None,
+			Content::None,
+			None,
+			vec![]
+		));
+		let item_id = CvModule::item_id_by_number(0).unwrap();
+		assert_ok!(CvModule::request_review(Origin::signed(1), item_id, Some(3)));
+		assert_ok!(CvModule::set_status_item(Origin::signed(3), item_id, Status::Allow, vec![]));
+		assert_eq!(CvModule::effective_status(item_id), Some(EffectiveStatus::Allow));
+
+		Timestamp::set_timestamp((NOW + 10) * 1000);
+		assert_eq!(CvModule::effective_status(item_id), Some(EffectiveStatus::Expired));
+		assert_eq!(CvModule::effective_status(H256::zero()), None);
+	});
+}
+
+#[test]
+fn cv_score_should_count_allowed_items() {
+	new_test_ext().execute_with(|| {
+		register_org(3);
+		pallet_sys_man::OrgProfiles::<Test>::insert(
+			3,
+			pallet_sys_man::OrgProfile { category: OrgCategory::University, reputation: 100 },
+		);
+		let allowed = create_item(1);
+		let pending = create_item(1);
+		assert_ok!(CvModule::request_review(Origin::signed(1), allowed, Some(3)));
+		assert_ok!(CvModule::set_status_item(Origin::signed(3), allowed, Status::Allow, vec![]));
+		assert_ok!(CvModule::request_review(Origin::signed(1), pending, Some(3)));
+
+		let score = CvModule::cv_score(1);
+		assert_eq!(score.items.len(), 1);
+		let item = &score.items[0];
+		assert_eq!(item.item_id, allowed);
+		assert_eq!(
+			item.context,
+			ItemScoreContext {
+				kind: Some(ItemKind::Education),
+				certificate_score: None,
+				reputation: 100,
+				age: NOW - 1_600_000_000,
+			}
+		);
+		// No certificate (50) at full weight for the kind, reputation and recency.
+		assert_eq!(item.score, 50);
+		assert_eq!(score.total, 50);
+
+		// The same degree two decades on is worth half.
+		Timestamp::set_timestamp((1_600_000_000 + 20 * 365 * 24 * 60 * 60) * 1000);
+		assert_eq!(CvModule::cv_score(1).total, 25);
+		assert_eq!(CvModule::cv_score(2), Default::default());
+	});
+}
+
+#[test]
+fn cv_score_should_count_each_certificate_once() {
+	new_test_ext().execute_with(|| {
+		let cid = issue_certificate(3, 1);
+		let number = CvModule::item_id();
+		assert_ok!(CvModule::create_item(
+			Origin::signed(1),
+			1,
+			degree(),
+			vec![],
+			None,
+			None,
+			Some(cid),
+			Content::None,
+			None,
+			vec![]
+		));
+		let first = CvModule::item_id_by_number(number).unwrap();
+		// A second item claiming the same certificate, as linked before one certificate could
+		// back only one item.
+		let second = create_item(1);
+		assert_ok!(CvModule::request_review(Origin::signed(1), second, Some(3)));
+		assert_ok!(CvModule::set_status_item(Origin::signed(3), second, Status::Allow, vec![]));
+		ItemById::<Test>::mutate(second, |item| item.as_mut().unwrap().certificate_id = Some(cid));
+		ItemsByCertificate::<Test>::append(cid, second);
+
+		let score = CvModule::cv_score(1);
+		assert_eq!(score.items.len(), 1);
+		assert_eq!(score.items[0].item_id, first);
+		assert_eq!(score.total, score.items[0].score);
+	});
+}
+
+#[test]
+fn amend_item_should_keep_versions_and_reopen_review() {
+	new_test_ext().execute_with(|| {
+		let item_id = create_item(1);
+		register_org(3);
+		assert_ok!(CvModule::request_review(Origin::signed(1), item_id, Some(3)));
+		assert_ok!(CvModule::set_status_item(Origin::signed(3), item_id, Status::Allow, vec![]));
+
+		let mut fields = degree();
+		fields.title = str2vec("MSc in Computer Science");
+		let amend = |who: u64, fields: ItemFields| {
+			CvModule::amend_item(
+				Origin::signed(who),
+				item_id,
+				fields,
+				str2vec("MSc in Computer Science"),
+				None,
+				None,
+				str2vec("Master, not bachelor"),
+				vec![],
+			)
+		};
+		assert_noop!(amend(2, fields.clone()), Error::<Test>::NotItemOwner);
+		assert_noop!(
+			amend(1, ItemFields { level: Some(1), ..fields.clone() }),
+			Error::<Test>::UnexpectedField
+		);
+
+		assert_ok!(amend(1, fields.clone()));
+		let item = CvModule::item_by_id(item_id).unwrap();
+		assert_eq!(item.version, 1);
+		assert_eq!(item.fields, Some(fields.clone()));
+		assert_eq!(item.metadata, str2vec("MSc in Computer Science"));
+
+		let versions = CvModule::item_versions(item_id);
+		assert_eq!(versions.len(), 2);
+		assert_eq!(versions[0].metadata_hash, BlakeTwo256::hash(b"BSc in Computer Science"));
+		assert_eq!(versions[0].fields, Some(degree()));
+		assert!(versions[0].note.is_empty());
+		assert_eq!(versions[1].metadata_hash, BlakeTwo256::hash(b"MSc in Computer Science"));
+		assert_eq!(versions[1].changed.account, 1);
+		assert_eq!(versions[1].note, str2vec("Master, not bachelor"));
+
+		// The verifier has to look at the amended item again.
+		assert_eq!(CvModule::item_status_by_item_id(item_id), Status::Pending);
+		let review = CvModule::review_by_item_id(item_id).unwrap();
+		assert_eq!(review.verifier, 3);
+		assert_eq!(review.decided, None);
+		assert_ok!(CvModule::set_status_item(Origin::signed(3), item_id, Status::Allow, vec![]));
+	});
+}
+
+#[test]
+fn access_grants_should_expire_and_be_revocable() {
+	new_test_ext().execute_with(|| {
+		let item_id = create_item(1);
+		let pending = propose_item(2, 1);
+		let scope = AccessScope::Item(item_id);
+		assert!(CvModule::can_view(1, item_id));
+		assert!(!CvModule::can_view(3, item_id));
+
+		assert_noop!(
+			CvModule::grant_access(Origin::signed(1), 3, scope, 1),
+			Error::<Test>::GrantExpired
+		);
+		assert_noop!(
+			CvModule::grant_access(Origin::signed(2), 3, scope, 10),
+			Error::<Test>::NotItemOwner
+		);
+		assert_noop!(
+			CvModule::grant_access(Origin::signed(1), 3, AccessScope::Item(pending), 10),
+			Error::<Test>::NotItemOwner
+		);
+
+		assert_ok!(CvModule::grant_access(Origin::signed(1), 3, scope, 10));
+		assert!(CvModule::can_view(3, item_id));
+		assert!(!CvModule::can_view(4, item_id));
+		System::set_block_number(10);
+		assert!(!CvModule::can_view(3, item_id));
+
+		// Kind grants cover every accepted item of the kind.
+		let kind = AccessScope::Kind(ItemKind::Education);
+		assert_ok!(CvModule::grant_access(Origin::signed(1), 4, kind, 20));
+		assert!(CvModule::can_view(4, item_id));
+		assert!(!CvModule::can_view(4, pending));
+		assert_ok!(CvModule::revoke_access(Origin::signed(1), 4, kind));
+		assert!(!CvModule::can_view(4, item_id));
+		System::assert_last_event(crate::mock::Event::CvModule(crate::Event::AccessRevoked(
+			1, 4, kind,
+		)));
+		assert_noop!(
+			CvModule::revoke_access(Origin::signed(1), 4, kind),
+			Error::<Test>::GrantNotFound
+		);
+
+		// Revoking the item drops the grants to it, but not those to its kind.
+		assert_ok!(CvModule::grant_access(Origin::signed(1), 3, scope, 30));
+		assert_ok!(CvModule::grant_access(Origin::signed(1), 4, kind, 30));
+		assert_eq!(CvModule::access_grant_count(1), 2);
+		assert_ok!(CvModule::revoke_item(Origin::signed(1), item_id, vec![]));
+		assert_eq!(CvModule::access_grant(1, (3, scope)), None);
+		assert!(CvModule::access_grant(1, (4, kind)).is_some());
+		assert_eq!(CvModule::access_grant_count(1), 1);
+		System::assert_has_event(crate::mock::Event::CvModule(crate::Event::AccessRevoked(
+			1, 3, scope,
+		)));
+	});
+}
+
+#[test]
+fn access_grants_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		let kind = AccessScope::Kind(ItemKind::Education);
+		for viewer in 2..=4 {
+			assert_ok!(CvModule::grant_access(Origin::signed(1), viewer, kind, 10));
+		}
+		assert_noop!(
+			CvModule::grant_access(Origin::signed(1), 5, kind, 10),
+			Error::<Test>::TooManyAccessGrants
+		);
+		// Extending a grant does not take another one.
+		assert_ok!(CvModule::grant_access(Origin::signed(1), 2, kind, 20));
+		assert_eq!(CvModule::access_grant_count(1), 3);
+
+		assert_ok!(CvModule::revoke_access(Origin::signed(1), 2, kind));
+		assert_ok!(CvModule::grant_access(Origin::signed(1), 5, kind, 10));
+	});
+}
+
+#[test]
+fn cv_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..4 {
+			create_item(1);
+		}
+		assert_noop!(
+			CvModule::create_item(
+				Origin::signed(1),
+				1,
+				degree(),
+				vec![],
+				None,
+				None,
+				None,
+				Content::None,
+				None,
+				vec![]
+			),
+			Error::<Test>::TooManyItems
+		);
+
+		// Proposals still reach the inbox, but wait for room in the CV.
+		let item_id = propose_item(2, 1);
+		assert_noop!(
+			CvModule::accept_item(Origin::signed(1), item_id),
+			Error::<Test>::TooManyItems
+		);
+		let first = CvModule::items_by_accountid(1)[0];
+		assert_ok!(CvModule::revoke_item(Origin::signed(1), first, vec![]));
+		assert_ok!(CvModule::accept_item(Origin::signed(1), item_id));
+	});
+}
+
+#[test]
+fn encrypted_item_keys_should_follow_access() {
+	new_test_ext().execute_with(|| {
+		let envelope = vec![7; ENVELOPE_LENGTH];
+		let create = |envelopes: Vec<(u64, Vec<u8>)>| {
+			CvModule::create_item(
+				Origin::signed(1),
+				1,
+				degree(),
+				vec![0xc1, 0xfe],
+				None,
+				None,
+				None,
+				Content::None,
+				None,
+				envelopes,
+			)
+		};
+		assert_noop!(
+			create(vec![(1, envelope.clone())]),
+			Error::<Test>::EncryptionKeyNotRegistered
+		);
+		assert_ok!(AccountModule::set_encryption_key(Origin::signed(1), [1; 32]));
+		assert_ok!(AccountModule::set_encryption_key(Origin::signed(3), [3; 32]));
+		assert_noop!(create(vec![(1, vec![7; 32])]), Error::<Test>::InvalidEnvelope);
+		assert_noop!(create(vec![(3, envelope.clone())]), Error::<Test>::SubjectKeyRequired);
+		assert_noop!(
+			create(vec![(1, envelope.clone()), (3, envelope.clone())]),
+			Error::<Test>::InvalidRecipient
+		);
+
+		assert_ok!(create(vec![(1, envelope.clone())]));
+		let item_id = CvModule::item_id_by_number(0).unwrap();
+		assert!(CvModule::item_by_id(item_id).unwrap().encrypted);
+		assert_eq!(CvModule::item_key(item_id, 1), Some(envelope.clone()));
+
+		// Viewers get the content key once they have access, and lose it with their access.
+		let share = |viewer: u64| {
+			CvModule::share_item_key(Origin::signed(1), item_id, viewer, envelope.clone())
+		};
+		assert_noop!(share(3), Error::<Test>::NoAccess);
+		let scope = AccessScope::Kind(ItemKind::Education);
+		assert_ok!(CvModule::grant_access(Origin::signed(1), 3, scope, 10));
+		assert_ok!(share(3));
+		assert_eq!(CvModule::item_key(item_id, 3), Some(envelope.clone()));
+		assert_ok!(CvModule::revoke_access(Origin::signed(1), 3, scope));
+		assert_eq!(CvModule::item_key(item_id, 3), None);
+		assert_eq!(CvModule::item_key(item_id, 1), Some(envelope.clone()));
+
+		let plaintext = create_item(1);
+		assert_noop!(
+			CvModule::share_item_key(Origin::signed(1), plaintext, 3, envelope.clone()),
+			Error::<Test>::ItemNotEncrypted
+		);
+
+		assert_ok!(CvModule::revoke_item(Origin::signed(1), item_id, vec![]));
+		assert_eq!(CvModule::item_key(item_id, 1), None);
+	});
+}
+
+#[test]
+fn migration_to_v1_should_route_items_about_others_to_the_inbox() {
+	use codec::Encode;
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		StorageHasher, Twox64Concat,
+	};
+
+	new_test_ext().execute_with(|| {
+		let legacy_key = |item: &[u8], key: Vec<u8>| {
+			let mut raw = storage_prefix(b"CvModule", item).to_vec();
+			raw.extend(Twox64Concat::hash(&key));
+			raw
+		};
+		// items as stored before hash ids, listed under the account that created them
+		let created = pallet_utils::WhoAndWhen::<Test>::new(1);
+		let legacy_item = |number: u32, user_id: u64| {
+			let (org_date, exp_date, certificate_id) = (None::<u64>, None::<u64>, None::<u32>);
+			let metadata = str2vec("BSc");
+			(number, user_id, created.clone(), org_date, exp_date, certificate_id, 0u32, metadata)
+				.encode()
+		};
+		unhashed::put_raw(&legacy_key(b"ItemById", 0u32.encode()), &legacy_item(0, 1));
+		unhashed::put_raw(&legacy_key(b"ItemById", 1u32.encode()), &legacy_item(1, 2));
+		unhashed::put(&legacy_key(b"ItemStatusByItemId", 0u32.encode()), &Status::Allow);
+		unhashed::put(&legacy_key(b"ItemsByAccountId", 1u64.encode()), &vec![0u32, 1]);
+		StorageVersion::new(0).put::<CvModule>();
+
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		// the item account 1 wrote about itself stays in its CV
+		let own = pallet_utils::Pallet::<Test>::derive_id(b"item", &1, 0);
+		assert_eq!(CvModule::item_id_by_number(0), Some(own));
+		assert!(CvModule::item_by_id(own).unwrap().is_accepted());
+		// Owners used to allow their own items, so an org has to review them again.
+		assert_eq!(CvModule::item_status_by_item_id(own), Status::Pending);
+		assert_eq!(CvModule::review_by_item_id(own), None);
+		assert_eq!(CvModule::items_by_accountid(1), vec![own]);
+
+		// the one it wrote about account 2 awaits its consent
+		let proposed = pallet_utils::Pallet::<Test>::derive_id(b"item", &1, 1);
+		let item = CvModule::item_by_id(proposed).unwrap();
+		assert_eq!((item.user_id, item.accepted), (2, None));
+		assert_eq!(CvModule::pending_items_by_accountid(2), vec![proposed]);
+		assert!(CvModule::items_by_accountid(2).is_empty());
+		assert_ok!(CvModule::accept_item(Origin::signed(2), proposed));
+		assert_eq!(CvModule::items_by_accountid(2), vec![proposed]);
+
+		assert_eq!(CvModule::on_chain_storage_version(), 1);
 	});
 }
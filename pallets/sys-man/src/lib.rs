@@ -17,7 +17,7 @@ pub mod pallet {
 	use codec::alloc::string::ToString;
 	use frame_support::{dispatch::DispatchResultWithPostInfo, ensure, pallet_prelude::*};
 	use frame_system::pallet_prelude::*;
-	use pallet_utils::{Role, Status};
+	use pallet_utils::{OrgCategory, Role, Status};
 	use scale_info::TypeInfo;
 	use serde::{Deserialize, Serialize};
 	use serde_json::{json, Value};
@@ -48,6 +48,16 @@ pub mod pallet {
 		pub metadata: Vec<u8>,
 	}
 
+	/// Standing of an organization, as assessed by system managers.
+	#[derive(Decode, Encode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct OrgProfile {
+		pub category: OrgCategory,
+		/// Reputation in the range `0..=MAX_REPUTATION`.
+		pub reputation: u8,
+	}
+
+	pub const MAX_REPUTATION: u8 = 100;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
@@ -72,6 +82,11 @@ pub mod pallet {
 	pub type OrgRevoked<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, SysManAccount<T>, OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn org_profile)]
+	pub type OrgProfiles<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, OrgProfile, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn sys_man_cnt)]
 	/// Keeps track of the number of system managers in existence.
@@ -108,6 +123,7 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		Approved { target_id: T::AccountId, metadata: Vec<u8>, approver: T::AccountId },
 		Revoked { target_id: T::AccountId, revoker: T::AccountId },
+		OrgProfileUpdated { org_id: T::AccountId, profile: OrgProfile },
 	}
 
 	#[pallet::error]
@@ -123,6 +139,7 @@ pub mod pallet {
 		RevokedSysManNotExist,
 		RevokedOrgNotExist,
 		OperationTypeInvalid,
+		InvalidReputation,
 	}
 
 	#[pallet::call]
@@ -290,6 +307,32 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		#[pallet::weight(10_000)]
+		pub fn set_org_profile(
+			origin: OriginFor<T>,
+			org_id: T::AccountId,
+			category: OrgCategory,
+			reputation: u8,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			// check permission of authority
+			let _ = Self::get_account(&sender, OperationType::SYS)?;
+
+			// ensure target is an approved org
+			let _ = Self::get_account(&org_id, OperationType::ORG)?;
+
+			ensure!(reputation <= MAX_REPUTATION, Error::<T>::InvalidReputation);
+
+			let profile = OrgProfile { category, reputation };
+
+			OrgProfiles::<T>::insert(&org_id, profile.clone());
+
+			Self::deposit_event(Event::<T>::OrgProfileUpdated { org_id, profile });
+
+			Ok(().into())
+		}
 	}
 
 	// private functions
@@ -3,7 +3,7 @@ use super::*;
 use crate::{mock::*, Error};
 use frame_support::{assert_noop, assert_ok};
 use frame_system as system;
-use pallet_utils::{Role, Status};
+use pallet_utils::{OrgCategory, Role, Status};
 use serde_json::{json, Value};
 
 fn str2vec(s: &str) -> Vec<u8> {
@@ -426,3 +426,77 @@ fn revoke_org_should_fail() {
 		);
 	})
 }
+
+#[test]
+fn set_org_profile_should_work() {
+	new_test_ext().execute_with(|| {
+		let authority_id = 0u64;
+		let org_id = 1u64;
+
+		let authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		let org = generate_test_account(Role::Organization, None, None, None);
+
+		SysMan::<Test>::insert(&authority_id, authority);
+
+		Org::<Test>::insert(&org_id, org);
+
+		assert_eq!(OrgProfiles::<Test>::get(&org_id), OrgProfile::default());
+
+		assert_ok!(SysManModule::set_org_profile(
+			Origin::signed(authority_id),
+			org_id,
+			OrgCategory::University,
+			80
+		));
+
+		let profile = OrgProfiles::<Test>::get(&org_id);
+
+		assert_eq!(profile.category, OrgCategory::University);
+		assert_eq!(profile.reputation, 80);
+	})
+}
+
+#[test]
+fn set_org_profile_should_fail() {
+	new_test_ext().execute_with(|| {
+		let authority_id = 0u64;
+		let org_id = 1u64;
+
+		let authority = generate_test_account(Role::SysMan, Some(0), None, None);
+
+		let org = generate_test_account(Role::Organization, None, None, None);
+
+		SysMan::<Test>::insert(&authority_id, authority);
+
+		// target is not an approved org
+		assert_noop!(
+			SysManModule::set_org_profile(
+				Origin::signed(authority_id),
+				org_id,
+				OrgCategory::University,
+				80
+			),
+			Error::<Test>::RevokedOrgNotExist
+		);
+
+		Org::<Test>::insert(&org_id, org);
+
+		// reputation out of range
+		assert_noop!(
+			SysManModule::set_org_profile(
+				Origin::signed(authority_id),
+				org_id,
+				OrgCategory::University,
+				MAX_REPUTATION + 1
+			),
+			Error::<Test>::InvalidReputation
+		);
+
+		// sender is not a system manager
+		assert_noop!(
+			SysManModule::set_org_profile(Origin::signed(org_id), org_id, OrgCategory::School, 10),
+			Error::<Test>::SysManNotExist
+		);
+	})
+}
@@ -7,9 +7,12 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod validation;
+
 use frame_support::inherent::Vec;
 
 pub type TypeID = u32;
+/// Seconds since the unix epoch.
 pub type UnixEpoch = u64;
 pub type String = Vec<u8>;
 
@@ -17,15 +20,23 @@ pub type String = Vec<u8>;
 pub mod pallet {
 	use codec::{Decode, Encode};
 	use frame_support::pallet_prelude::*;
-	use frame_support::traits::Currency;
+	use frame_support::traits::{Currency, ReservableCurrency};
 	use frame_system as system;
 
 	use scale_info::TypeInfo;
 	#[cfg(feature = "std")]
 	use serde::{Deserialize, Serialize};
-	use sp_runtime::RuntimeDebug;
+	use sp_runtime::{
+		traits::{Hash, UniqueSaturatedInto, Zero},
+		RuntimeDebug,
+	};
 	use sp_std::prelude::*;
 
+	use super::{
+		validation::{is_valid_hypercore_key, is_valid_ipfs_cid},
+		TypeID, UnixEpoch,
+	};
+
 	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 	#[scale_info(bounds(), skip_type_params(T))]
 	pub struct WhoAndWhen<T: Config> {
@@ -57,6 +68,23 @@ pub mod pallet {
 		User,
 	}
 
+	#[derive(Encode, Decode, Ord, PartialOrd, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum OrgCategory {
+		University,
+		School,
+		Company,
+		TrainingProvider,
+		Government,
+		Other,
+	}
+
+	impl Default for OrgCategory {
+		fn default() -> Self {
+			Self::Other
+		}
+	}
+
 	#[derive(Encode, Decode, Ord, PartialOrd, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub enum Status {
@@ -71,6 +99,20 @@ pub mod pallet {
 		}
 	}
 
+	/// Outcome of something awaiting an account's approval.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum ApprovalStatus {
+		Pending,
+		Allow,
+		Deny,
+	}
+
+	impl Default for ApprovalStatus {
+		fn default() -> Self {
+			Self::Pending
+		}
+	}
+
 	impl<AccountId> User<AccountId> {
 		pub fn maybe_account(self) -> Option<AccountId> {
 			if let User::Account(account_id) = self {
@@ -136,7 +178,7 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// The currency mechanism.
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
 	}
 
 	#[pallet::pallet]
@@ -176,6 +218,8 @@ pub mod pallet {
 		RawContentTypeNotSupported,
 		/// `Hyper` content type is not yet supported.
 		HypercoreContentTypeNotSupported,
+		/// Hypercore key is invalid.
+		InvalidHypercoreKey,
 		/// Space handle is too short.
 		HandleIsTooShort,
 		/// Space handle is too long.
@@ -185,4 +229,37 @@ pub mod pallet {
 		/// Content type is `None`.
 		ContentIsEmpty,
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Globally unique id of the `nonce`-th record of kind `domain`, created by `issuer`.
+		///
+		/// The genesis hash keeps ids of different networks apart, so exported records of a
+		/// testnet never collide with mainnet ones.
+		pub fn derive_id(domain: &[u8], issuer: &T::AccountId, nonce: TypeID) -> T::Hash {
+			let genesis = <system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+			T::Hashing::hash_of(&(genesis, domain, issuer, nonce))
+		}
+
+		/// The current time of the chain as reported by `pallet_timestamp`.
+		pub fn unix_now() -> UnixEpoch {
+			let millis: u64 = <pallet_timestamp::Pallet<T>>::now().unique_saturated_into();
+			millis / 1000
+		}
+
+		/// Ensures that off-chain content points to a well-formed IPFS CID or Hypercore key.
+		pub fn ensure_content_is_valid(content: &Content) -> DispatchResult {
+			match content {
+				Content::None => Ok(()),
+				Content::Raw(_) => Err(Error::<T>::RawContentTypeNotSupported.into()),
+				Content::IPFS(cid) => {
+					ensure!(is_valid_ipfs_cid(cid), Error::<T>::InvalidIpfsCid);
+					Ok(())
+				},
+				Content::Hyper(key) => {
+					ensure!(is_valid_hypercore_key(key), Error::<T>::InvalidHypercoreKey);
+					Ok(())
+				},
+			}
+		}
+	}
 }
@@ -1,20 +1,87 @@
-use crate::{mock::*, Error};
+//! pallet-utils functionalities test
+use crate::{mock::*, validation, Content, Error};
 use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+
+fn str2vec(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
+
+#[test]
+fn valid_ipfs_cids_should_be_accepted() {
+	new_test_ext().execute_with(|| {
+		for cid in [
+			"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+			"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+			"bafkreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy",
+			"zb2rhe5P4gXftAwvA4eXQ5HJwsER2owDyS9sKaQRRVQPn93bA",
+		] {
+			assert_ok!(Utils::ensure_content_is_valid(&Content::IPFS(str2vec(cid))));
+		}
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn invalid_ipfs_cids_should_be_rejected() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		for cid in [
+			"",
+			"hello",
+			// invalid base58 character
+			"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0",
+			// truncated digest
+			"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd",
+			// CIDv0 with a SHA3-256 multihash
+			"W1nDXnssFjx1Y1b6BV3Mkv6Q2Ns1VLongHRfuEjmEvtjk7",
+			// CIDv0 with a 31-byte digest followed by a stray byte
+			"QmHssu7KjrBdiLwZYLCVo4o9sniou5DUKNhUAvWaYDZQTM",
+			// CIDv0 claiming a 33-byte digest
+			"QmtLWe6uF3mjBt5j7Aer9fBbUqDoBihtTZfa5aERPkbJwu",
+		] {
+			assert_noop!(
+				Utils::ensure_content_is_valid(&Content::IPFS(str2vec(cid))),
+				Error::<Test>::InvalidIpfsCid
+			);
+		}
+
+		// too long to be decoded
+		let mut cid = str2vec("z");
+		cid.resize(validation::MAX_CID_LENGTH + 1, b'2');
+		assert_noop!(
+			Utils::ensure_content_is_valid(&Content::IPFS(cid)),
+			Error::<Test>::InvalidIpfsCid
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn hypercore_keys_should_be_validated() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		let key = str2vec("7b0a1c8d1c2d6c62bc62f5b1d8dfa2b1d2c0b8f2d8c4e1b0a3f5e6d7c8b9a0f1");
+		assert_ok!(Utils::ensure_content_is_valid(&Content::Hyper(key)));
+
+		assert_noop!(
+			Utils::ensure_content_is_valid(&Content::Hyper(str2vec("not-a-key"))),
+			Error::<Test>::InvalidHypercoreKey
+		);
+		assert_noop!(
+			Utils::ensure_content_is_valid(&Content::Raw(vec![1, 2, 3])),
+			Error::<Test>::RawContentTypeNotSupported
+		);
+	});
+}
+
+#[test]
+fn derived_ids_should_be_unique() {
+	new_test_ext().execute_with(|| {
+		let id = Utils::derive_id(b"certificate", &1, 0);
+		assert_eq!(id, Utils::derive_id(b"certificate", &1, 0));
+		assert_ne!(id, Utils::derive_id(b"certificate", &1, 1));
+		assert_ne!(id, Utils::derive_id(b"certificate", &2, 0));
+		assert_ne!(id, Utils::derive_id(b"item", &1, 0));
+
+		// the same record on another network gets another id
+		frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+		assert_ne!(id, Utils::derive_id(b"certificate", &1, 0));
 	});
 }
@@ -0,0 +1,132 @@
+//! Validation of off-chain content identifiers.
+
+use sp_std::vec::Vec;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Multihash code of SHA2-256, the only hash function allowed in CIDv0.
+const SHA2_256: u64 = 0x12;
+/// Digest length of SHA2-256 in bytes.
+const SHA2_256_LENGTH: usize = 32;
+
+/// Longest CID accepted, checked before decoding as base58 decoding is quadratic in the length.
+pub const MAX_CID_LENGTH: usize = 128;
+
+/// Tells whether `cid` is a well-formed IPFS CIDv0 or CIDv1.
+///
+/// CIDv0 are base58btc encoded SHA2-256 multihashes (`Qm...`). CIDv1 are accepted in their
+/// base32 (`b...`), base58btc (`z...`) and base16 (`f...`) multibase encodings.
+pub fn is_valid_ipfs_cid(cid: &[u8]) -> bool {
+	if cid.len() > MAX_CID_LENGTH {
+		return false
+	}
+	match cid {
+		[b'Q', b'm', ..] if cid.len() == 46 =>
+			decode_base58(cid).map_or(false, |bytes| is_valid_cid_v0(&bytes)),
+		[b'b', rest @ ..] => decode_base32(rest).map_or(false, |bytes| is_valid_cid_v1(&bytes)),
+		[b'z', rest @ ..] => decode_base58(rest).map_or(false, |bytes| is_valid_cid_v1(&bytes)),
+		[b'f', rest @ ..] => decode_base16(rest).map_or(false, |bytes| is_valid_cid_v1(&bytes)),
+		_ => false,
+	}
+}
+
+/// Tells whether `key` is a hex encoded 32-byte Hypercore public key.
+pub fn is_valid_hypercore_key(key: &[u8]) -> bool {
+	key.len() == 64 && key.iter().all(u8::is_ascii_hexdigit)
+}
+
+/// `<SHA2-256 code><digest length = 32><digest>`
+fn is_valid_cid_v0(bytes: &[u8]) -> bool {
+	matches!(
+		parse_multihash(bytes),
+		Some((SHA2_256, digest, rest)) if digest.len() == SHA2_256_LENGTH && rest.is_empty()
+	)
+}
+
+/// `<version = 1><content codec><multihash>`
+fn is_valid_cid_v1(bytes: &[u8]) -> bool {
+	let rest = match read_varint(bytes) {
+		Some((1, rest)) => rest,
+		_ => return false,
+	};
+	let rest = match read_varint(rest) {
+		Some((_codec, rest)) => rest,
+		None => return false,
+	};
+	matches!(parse_multihash(rest), Some((_, _, rest)) if rest.is_empty())
+}
+
+/// `<hash function code><digest length><digest>`, returning the code, the digest and the trailing
+/// bytes.
+fn parse_multihash(bytes: &[u8]) -> Option<(u64, &[u8], &[u8])> {
+	let (code, rest) = read_varint(bytes)?;
+	let (len, rest) = read_varint(rest)?;
+	let len = usize::try_from(len).ok()?;
+	if len == 0 || rest.len() < len {
+		return None
+	}
+	let (digest, rest) = rest.split_at(len);
+	Some((code, digest, rest))
+}
+
+/// Reads an unsigned LEB128 varint as used by multiformats.
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+	let mut value = 0u64;
+	for (i, byte) in bytes.iter().enumerate().take(9) {
+		value |= u64::from(byte & 0x7f) << (7 * i);
+		if byte & 0x80 == 0 {
+			return Some((value, &bytes[i + 1..]))
+		}
+	}
+	None
+}
+
+fn decode_base58(input: &[u8]) -> Option<Vec<u8>> {
+	let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+	for c in input {
+		let mut carry = BASE58_ALPHABET.iter().position(|a| a == c)? as u32;
+		for byte in bytes.iter_mut().rev() {
+			carry += u32::from(*byte) * 58;
+			*byte = carry as u8;
+			carry >>= 8;
+		}
+		while carry > 0 {
+			bytes.insert(0, carry as u8);
+			carry >>= 8;
+		}
+	}
+	let zeros = input.iter().take_while(|c| **c == b'1').count();
+	let mut decoded = Vec::with_capacity(zeros + bytes.len());
+	decoded.resize(zeros, 0);
+	decoded.extend(bytes);
+	Some(decoded)
+}
+
+fn decode_base32(input: &[u8]) -> Option<Vec<u8>> {
+	let mut decoded = Vec::with_capacity(input.len() * 5 / 8);
+	let (mut buffer, mut bits) = (0u32, 0u32);
+	for c in input {
+		buffer = (buffer << 5) | BASE32_ALPHABET.iter().position(|a| a == c)? as u32;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			decoded.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+	Some(decoded)
+}
+
+fn decode_base16(input: &[u8]) -> Option<Vec<u8>> {
+	if input.len() % 2 != 0 {
+		return None
+	}
+	input
+		.chunks(2)
+		.map(|pair| {
+			let digit = |c: u8| (c as char).to_digit(16);
+			Some((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+		})
+		.collect()
+}
@@ -26,7 +26,7 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{KeyOwnerProofSystem, Randomness, StorageInfo},
+	traits::{Contains, KeyOwnerProofSystem, Randomness, StorageInfo},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
@@ -97,7 +97,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -139,11 +139,29 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 }
 
+/// Certificate tokens are soulbound: they are minted and burned by `pallet_certificate` only.
+/// Direct `pallet_uniques` calls are limited to managing metadata.
+pub struct BaseFilter;
+impl Contains<Call> for BaseFilter {
+	fn contains(call: &Call) -> bool {
+		match call {
+			Call::Uniques(call) => matches!(
+				call,
+				pallet_uniques::Call::set_metadata { .. } |
+					pallet_uniques::Call::clear_metadata { .. } |
+					pallet_uniques::Call::set_class_metadata { .. } |
+					pallet_uniques::Call::clear_class_metadata { .. }
+			),
+			_ => true,
+		}
+	}
+}
+
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Config for Runtime {
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = BlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -273,6 +291,33 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const ClassDeposit: Balance = 0;
+	pub const InstanceDeposit: Balance = 0;
+	pub const MetadataDepositBase: Balance = 0;
+	pub const MetadataDepositPerByte: Balance = 0;
+	pub const UniquesStringLimit: u32 = 128;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 64;
+}
+
+impl pallet_uniques::Config for Runtime {
+	type Event = Event;
+	type ClassId = pallet_utils::TypeID;
+	type InstanceId = pallet_utils::TypeID;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type ClassDeposit = ClassDeposit;
+	type InstanceDeposit = InstanceDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type AttributeDepositBase = MetadataDepositBase;
+	type DepositPerByte = MetadataDepositPerByte;
+	type StringLimit = UniquesStringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_utils::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
@@ -286,12 +331,48 @@ impl pallet_sys_man::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MaxItems: u32 = 256;
+	pub const MaxPendingItems: u32 = 64;
+	pub const ProposalDeposit: Balance = 1_000_000_000;
+	pub const MaxFieldLength: u32 = 256;
+	/// Items may be valid for up to 50 years.
+	pub const MaxFutureDate: pallet_utils::UnixEpoch = 50 * 365 * 24 * 60 * 60;
+	pub const MaxItemKeys: u32 = 32;
+	pub const MaxAccessGrants: u32 = 64;
+}
+
 impl pallet_cv::Config for Runtime {
 	type Event = Event;
+	type MaxItems = MaxItems;
+	type MaxPendingItems = MaxPendingItems;
+	type ProposalDeposit = ProposalDeposit;
+	type MaxFieldLength = MaxFieldLength;
+	type MaxFutureDate = MaxFutureDate;
+	type MaxItemKeys = MaxItemKeys;
+	type MaxAccessGrants = MaxAccessGrants;
+	type CvScoringPolicy = pallet_cv::scoring::DefaultScoringPolicy;
+}
+
+parameter_types! {
+	pub const CertificateDepositBase: Balance = 1_000_000_000;
+	pub const CertificateDepositPerByte: Balance = 10_000_000;
+	/// Orgs have 30 days to answer a certificate request.
+	pub const RequestTimeout: pallet_utils::UnixEpoch = 30 * 24 * 60 * 60;
+	pub const RequestDeposit: Balance = 1_000_000_000;
+	pub const MaxPendingRequests: u32 = 256;
 }
 
 impl pallet_certificate::Config for Runtime {
 	type Event = Event;
+	type ScoringPolicy = pallet_certificate::scoring::DefaultScoringPolicy;
+	type Nfts = Uniques;
+	type CertificateDepositBase = CertificateDepositBase;
+	type CertificateDepositPerByte = CertificateDepositPerByte;
+	type RequestTimeout = RequestTimeout;
+	type RequestDeposit = RequestDeposit;
+	type MaxPendingRequests = MaxPendingRequests;
+	type OnRevoke = Cv;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -309,6 +390,7 @@ construct_runtime!(
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
+		Uniques: pallet_uniques,
 		//governance pallets
 		//scv-chian pallets
 		Account: pallet_account,
@@ -345,8 +427,17 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
+	Migrations,
 >;
 
+/// Storage migrations run on runtime upgrade, in order.
+///
+/// CV items link to certificates, so certificates are migrated first.
+pub type Migrations = (
+	pallet_certificate::migrations::v1::MigrateToV1<Runtime>,
+	pallet_cv::migrations::v1::MigrateToV1<Runtime>,
+);
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -478,6 +569,105 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_certificate_rpc_runtime_api::CertificateApi<
+		Block,
+		AccountId,
+		pallet_certificate::Certificate<Runtime>,
+		pallet_certificate::CertificateClass<Runtime>,
+		Hash,
+	> for Runtime {
+		fn certificate(cid: Hash) -> Option<pallet_certificate::Certificate<Runtime>> {
+			Certificate::certificate_by_id(cid)
+		}
+
+		fn certificate_id(number: pallet_utils::TypeID) -> Option<Hash> {
+			Certificate::certificate_id_by_number(number)
+		}
+
+		fn class(
+			class_id: pallet_utils::TypeID,
+		) -> Option<pallet_certificate::CertificateClass<Runtime>> {
+			Certificate::class_by_id(class_id)
+		}
+
+		fn certificates_by_document(document: pallet_certificate::DocumentHash) -> Vec<Hash> {
+			Certificate::certificates_by_document(document)
+		}
+
+		fn verify(cid: Hash, holder: AccountId) -> pallet_certificate::Verdict {
+			Certificate::verify(cid, holder)
+		}
+
+		fn status_list(
+			org: AccountId,
+			purpose: pallet_certificate::StatusPurpose,
+		) -> Vec<u8> {
+			Certificate::status_list(org, purpose)
+		}
+
+		fn verify_disclosure(
+			cid: Hash,
+			key: Vec<u8>,
+			value: Vec<u8>,
+			salt: Vec<u8>,
+			proof: Vec<Hash>,
+		) -> bool {
+			Certificate::verify_disclosure(cid, key, value, salt, proof)
+		}
+
+		fn verify_batch_leaf(
+			batch_id: pallet_utils::TypeID,
+			leaf: Hash,
+			proof: Vec<Hash>,
+		) -> pallet_certificate::BatchLeafStatus {
+			Certificate::verify_batch_leaf(batch_id, leaf, proof)
+		}
+	}
+
+	impl pallet_cv_rpc_runtime_api::CvApi<
+		Block,
+		AccountId,
+		pallet_cv::Item<Runtime>,
+		pallet_cv::ItemVersion<Runtime>,
+		pallet_cv::ItemTombstone<Runtime>,
+		Hash,
+	> for Runtime {
+		fn item(item_id: Hash) -> Option<pallet_cv::Item<Runtime>> {
+			Cv::item_by_id(item_id)
+		}
+
+		fn item_status(item_id: Hash) -> Option<pallet_cv::EffectiveStatus> {
+			Cv::effective_status(item_id)
+		}
+
+		fn item_versions(item_id: Hash) -> Vec<pallet_cv::ItemVersion<Runtime>> {
+			Cv::item_versions(item_id)
+		}
+
+		fn item_tombstone(item_id: Hash) -> Option<pallet_cv::ItemTombstone<Runtime>> {
+			Cv::tombstone_by_item_id(item_id)
+		}
+
+		fn can_view(viewer: AccountId, item_id: Hash) -> bool {
+			Cv::can_view(viewer, item_id)
+		}
+
+		fn item_key(item_id: Hash, reader: AccountId) -> Option<Vec<u8>> {
+			Cv::item_key(item_id, reader)
+		}
+
+		fn items(
+			account: AccountId,
+			filter: pallet_cv::ItemFilter,
+		) -> Vec<pallet_cv::Item<Runtime>> {
+			Cv::filter_items(account, filter)
+		}
+
+		fn score(account: AccountId) -> pallet_cv::scoring::CvScore<Hash> {
+			Cv::cv_score(account)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -539,3 +729,6 @@ impl_runtime_apis! {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests;
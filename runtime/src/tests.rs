@@ -0,0 +1,42 @@
+//! Runtime configuration tests.
+
+use super::*;
+use sp_runtime::MultiAddress;
+
+fn uniques_call_allowed(call: pallet_uniques::Call<Runtime>) -> bool {
+	<Runtime as frame_system::Config>::BaseCallFilter::contains(&Call::Uniques(call))
+}
+
+#[test]
+fn certificate_tokens_should_not_be_transferable() {
+	let holder = AccountId::from([2; 32]);
+	let (class, instance) = (0, 0);
+
+	assert!(!uniques_call_allowed(pallet_uniques::Call::transfer {
+		class,
+		instance,
+		dest: MultiAddress::Id(holder.clone()),
+	}));
+	assert!(!uniques_call_allowed(pallet_uniques::Call::approve_transfer {
+		class,
+		instance,
+		delegate: MultiAddress::Id(holder.clone()),
+	}));
+	// Nor can the org hand its collection, or the power to thaw tokens, to someone else.
+	assert!(!uniques_call_allowed(pallet_uniques::Call::thaw { class, instance }));
+	assert!(!uniques_call_allowed(pallet_uniques::Call::transfer_ownership {
+		class,
+		owner: MultiAddress::Id(holder.clone()),
+	}));
+	assert!(!uniques_call_allowed(pallet_uniques::Call::burn {
+		class,
+		instance,
+		check_owner: None,
+	}));
+
+	// Wallets still get to display them.
+	assert!(uniques_call_allowed(pallet_uniques::Call::clear_metadata { class, instance }));
+	assert!(<Runtime as frame_system::Config>::BaseCallFilter::contains(&Call::System(
+		frame_system::Call::remark { remark: vec![] }
+	)));
+}